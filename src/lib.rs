@@ -13,9 +13,15 @@
 //! }
 //! ```
 //!
+//! Your crate needs to depend on `quickcheck` itself, under that name.
+//! If it doesn't, the generated paths to `::quickcheck` won't resolve.
+//!
 //! You can customise field generation by either:
 //! - providing a callable that accepts [`&mut quickcheck::Gen`](https://docs.rs/quickcheck/latest/quickcheck/struct.Gen.html).
 //! - always using the default value
+//! - always using a given value, with `default = ...`.
+//!   Unlike `default`, this doesn't need the member's type to implement `Default`.
+//!   It's evaluated inside the impl, so it can refer to e.g. `Self::VERSION`.
 //! ```
 //! # use derive_quickcheck_arbitrary::Arbitrary;
 //! # mod num { pub fn clamp(input: usize, min: usize, max: usize) -> usize { todo!() } }
@@ -27,6 +33,392 @@
 //!     name: String,
 //!     #[arbitrary(default)]
 //!     always_false: bool,
+//!     #[arbitrary(default = 3)]
+//!     always_three: u8,
+//! }
+//! ```
+//!
+//! A generator's parameter is the `Gen`, whatever you name it, so its methods can be called directly.
+//! Generators can't capture anything, so there's no other `Gen` in scope:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! struct Yakshaver {
+//!     #[arbitrary(gen(|rng| *rng.choose(&["Jess", "Alex"]).unwrap()))]
+//!     name: &'static str,
+//!     #[arbitrary(gen(|rng| rng.size()))]
+//!     max_yaks: usize,
+//! }
+//! ```
+//!
+//! `choose` picks from any slice, so tables in a `const` or `static` work too,
+//! and a `static` table can hand out `&'static` references.
+//! It returns `None` for an empty slice, so that `unwrap` would panic:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Debug)]
+//! struct Breed {
+//!     name: &'static str,
+//! }
+//!
+//! const NAMES: [&str; 2] = ["Jess", "Alex"];
+//! static BREEDS: [Breed; 2] = [Breed { name: "Wild" }, Breed { name: "Domestic" }];
+//!
+//! #[derive(Clone, Arbitrary)]
+//! struct Yakshaver {
+//!     #[arbitrary(gen(|g| *g.choose(&NAMES).unwrap()))]
+//!     name: &'static str,
+//!     #[arbitrary(gen(|g| g.choose(&BREEDS).unwrap()))]
+//!     favourite: &'static Breed,
+//! }
+//! ```
+//!
+//! `gen = GENERATOR` is the same as `gen(GENERATOR)`, which reads better for a path,
+//! e.g. to a generator kept in a `const` or `static` fn pointer:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! # use quickcheck::{Arbitrary, Gen};
+//! const HORNS: fn(&mut Gen) -> u8 = |g| u8::arbitrary(g) % 3;
+//!
+//! #[derive(Clone, Arbitrary)]
+//! struct Yak {
+//!     #[arbitrary(gen = HORNS)]
+//!     horns: u8,
+//! }
+//! ```
+//!
+//! `with_gen(TYPE)` on the item hands generators a `TYPE` instead of the `Gen`.
+//! Each call makes a fresh one with `From<&mut Gen>`, so a wrapper borrowing the `Gen`
+//! is written with an elided lifetime.
+//! Members without a generator still use the `Gen`:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! # use quickcheck::{Arbitrary, Gen};
+//! struct Shears<'a>(&'a mut Gen);
+//!
+//! impl<'a> From<&'a mut Gen> for Shears<'a> {
+//!     fn from(g: &'a mut Gen) -> Self {
+//!         Self(g)
+//!     }
+//! }
+//!
+//! impl Shears<'_> {
+//!     fn clip(&mut self) -> u8 {
+//!         u8::arbitrary(self.0) % 10
+//!     }
+//! }
+//!
+//! #[derive(Clone, Arbitrary)]
+//! #[arbitrary(with_gen(Shears<'_>))]
+//! struct Yak {
+//!     #[arbitrary(gen(|shears| shears.clip()))]
+//!     fleece: u8,
+//!     name: String,
+//! }
+//! ```
+//!
+//! Generators must return exactly the field's type, which may be generic:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! # use quickcheck::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! #[arbitrary(where(T: Arbitrary + From<u8>))]
+//! struct Yakshaver<T> {
+//!     #[arbitrary(gen(|g| u8::arbitrary(g).into()))]
+//!     id: T,
+//! }
+//! ```
+//!
+//! Fallible generators can return a `Result` with `try_gen`.
+//! They're tried up to 100 times, and then the member is its `Default`, or the `or` value:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! # use quickcheck::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! struct Yakshaver {
+//!     #[arbitrary(try_gen(|g| u8::try_from(u16::arbitrary(g))))]
+//!     yaks_shaved: u8,
+//!     #[arbitrary(try_gen(|g| String::arbitrary(g).parse(), or = 7))]
+//!     id: u32,
+//! }
+//! ```
+//!
+//! Collections can be generated with a length in a given range, and aliased
+//! collection types are fine - anything that implements [`FromIterator`](https://doc.rust-lang.org/std/iter/trait.FromIterator.html) will do.
+//! Open-ended ranges like `len(1..)` are capped at the generator's [`size`](https://docs.rs/quickcheck/latest/quickcheck/struct.Gen.html#method.size).
+//! `len = N` is exactly `N` elements.
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! type Names = Vec<String>;
+//!
+//! #[derive(Clone, Arbitrary)]
+//! struct Yakshaver {
+//!     #[arbitrary(len(1..=3))]
+//!     names: Names,
+//!     #[arbitrary(len = 16)]
+//!     id: Vec<u8>,
+//! }
+//! ```
+//!
+//! On a `String`, `len` counts `char`s.
+//! `byte_len` counts bytes instead, ending in ASCII `char`s where a wider one wouldn't fit:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! struct Yak {
+//!     #[arbitrary(len(1..=8))]
+//!     nickname: String,
+//!     #[arbitrary(byte_len(..=255))]
+//!     dns_label: String,
+//! }
+//! ```
+//!
+//! With `size = N` on the item, the whole value is generated from a fresh `Gen::new(N)`,
+//! overriding the caller's size (and its random source).
+//! So e.g. collections stay short however big the test's `Gen` is:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! #[arbitrary(size = 10)]
+//! struct Herd {
+//!     yaks: Vec<String>,
+//! }
+//! ```
+//!
+//! Elements can be made pairwise distinct, preserving generation order.
+//! This only needs [`PartialEq`](https://doc.rust-lang.org/std/cmp/trait.PartialEq.html), unlike a `HashSet`:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! struct Yakshaver {
+//!     #[arbitrary(len(..=3), distinct)]
+//!     favourite_yaks: Vec<String>,
+//! }
+//! ```
+//!
+//! Collections can start with a custom element, followed by `tail_len` arbitrary ones:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! struct Csv {
+//!     #[arbitrary(head(gen(|_| String::from("name,age"))), tail_len(0..=4))]
+//!     lines: Vec<String>,
+//! }
+//! ```
+//!
+//! Or be made entirely of custom elements, with a length in a given range:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! struct Yakshaver {
+//!     #[arbitrary(repeat_with(gen(|g| u8::arbitrary(g) % 10), len(1..=10)))]
+//!     yak_ids: Vec<u8>,
+//! }
+//! ```
+//!
+//! Or be collected from any iterator with `gen_iter`, which may borrow the `Gen`.
+//! The member must implement `FromIterator` for the iterator's items:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! # use quickcheck::Arbitrary;
+//! use std::collections::BTreeMap;
+//!
+//! #[derive(Clone, Arbitrary)]
+//! struct Herd {
+//!     #[arbitrary(gen_iter(|g| (0..5).map(|_| u8::arbitrary(g))))]
+//!     ages: Vec<u8>,
+//!     #[arbitrary(gen_iter(|g| (1..=3).map(|id| (id, bool::arbitrary(g)))))]
+//!     shorn: BTreeMap<u32, bool>,
+//! }
+//! ```
+//!
+//! Strategies can be shared, and shrink too, with `gen_fn`.
+//! It takes any value with `generate` and `shrink` methods like these.
+//! A proc-macro crate can't export a trait for them, so they're called by name:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! use quickcheck::{Arbitrary as _, Gen};
+//!
+//! struct Below(u8);
+//!
+//! impl Below {
+//!     fn generate(&self, g: &mut Gen) -> u8 {
+//!         u8::arbitrary(g) % self.0
+//!     }
+//!     fn shrink(&self, it: &u8) -> Box<dyn Iterator<Item = u8>> {
+//!         Box::new((0..*it).rev())
+//!     }
+//! }
+//!
+//! const PERCENT: Below = Below(101);
+//!
+//! #[derive(Clone, Arbitrary)]
+//! struct Yakshaver {
+//!     #[arbitrary(gen_fn = PERCENT)]
+//!     progress: u8,
+//! }
+//! ```
+//!
+//! Float members can be kept finite, regenerating until they aren't `NaN` or infinite.
+//! Use `finite_floats` on the item to apply this to every `f32` and `f64` member:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! #[arbitrary(finite_floats)]
+//! struct Yak {
+//!     weight: f64,
+//!     height: f32,
+//! }
+//!
+//! #[derive(Clone, Arbitrary)]
+//! struct Yakshaver {
+//!     #[arbitrary(finite)]
+//!     speed: f64,
+//! }
+//! ```
+//!
+//! `Box`, `Rc` and `Arc` of `str` or `[T]` are generated from a `String` or `Vec<T>`,
+//! and held constant while shrinking.
+//! So is a `Box<[T; N]>`, which is filled with an arbitrary `T` for each element.
+//! `PhantomPinned` and `()` members are built directly.
+//! `Cell`, `RefCell`, `Mutex` and `RwLock` members are built with `new` from an arbitrary inner value,
+//! and held constant while shrinking.
+//!
+//! `flatten` generates a nested struct with the outer [`Gen`](https://docs.rs/quickcheck/latest/quickcheck/struct.Gen.html), as is,
+//! so it sees the outer item's `size`.
+//! That's the same as no attribute for now, but marks where sizes will be passed down:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! struct Common {
+//!     name: String,
+//! }
+//!
+//! #[derive(Clone, Arbitrary)]
+//! struct Yak {
+//!     #[arbitrary(flatten)]
+//!     common: Common,
+//!     horns: u8,
+//! }
+//! ```
+//!
+//! With `respect_serde_skip` on the item, members marked `#[serde(skip)]` are treated as `#[arbitrary(default)]`,
+//! unless they have a strategy of their own.
+//! This is opt-in, and only looks for a bare `skip`:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary, serde::Serialize)]
+//! #[arbitrary(respect_serde_skip)]
+//! struct Yak {
+//!     name: String,
+//!     #[serde(skip)]
+//!     cached_len: Option<usize>,
+//! }
+//! ```
+//!
+//! `range` keeps an integer member within bounds, which suits newtypes:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! struct Port(#[arbitrary(range(1..=65535))] u16);
+//!
+//! #[derive(Clone, Arbitrary)]
+//! struct Yak {
+//!     #[arbitrary(range(-40..50))]
+//!     temperature: i8,
+//! }
+//! ```
+//!
+//! `each` applies its arguments to every element of an array:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! struct Pin {
+//!     #[arbitrary(each(range(0..=9)))]
+//!     digits: [u8; 4],
+//! }
+//! ```
+//!
+//! Arrays don't implement `Arbitrary`, so they need `each` or `gen`.
+//! Both infer the length from the member's type, so it may be an associated `const`.
+//! Naming it as a const argument, as in `digits::<{ Self::LEN }>`, only works for non-generic items,
+//! because stable Rust doesn't allow a generic `Self` in array lengths:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! # use quickcheck::{Arbitrary, Gen};
+//! fn digits<const N: usize>(g: &mut Gen) -> [u8; N] {
+//!     std::array::from_fn(|_| u8::arbitrary(g) % 10)
+//! }
+//!
+//! #[derive(Clone, Arbitrary)]
+//! struct Pin {
+//!     #[arbitrary(each(range(0..=9)))]
+//!     digits: [u8; Self::LEN],
+//!     #[arbitrary(gen(|g| digits::<{ Self::LEN }>(g)))]
+//!     backup: [u8; Self::LEN],
+//! }
+//!
+//! impl Pin {
+//!     const LEN: usize = 4;
+//! }
+//! ```
+//!
+//! [`SystemTime`](https://doc.rust-lang.org/std/time/struct.SystemTime.html) doesn't implement `Arbitrary`,
+//! but `system_time` will generate one some seconds after the `UNIX_EPOCH`.
+//! There's no portable way to build an [`Instant`](https://doc.rust-lang.org/std/time/struct.Instant.html),
+//! so those must use `gen`:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! use std::time::{Instant, SystemTime};
+//!
+//! #[derive(Clone, Arbitrary)]
+//! struct Yakshave {
+//!     #[arbitrary(system_time)]
+//!     started: SystemTime,
+//!     #[arbitrary(gen(|_| Instant::now()))]
+//!     timer: Instant,
+//! }
+//! ```
+//!
+//! `duration` generates a [`Duration`](https://doc.rust-lang.org/std/time/struct.Duration.html) of whole seconds,
+//! up to `u32::MAX` of them, or within a `secs` range:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! use std::time::Duration;
+//!
+//! #[derive(Clone, Arbitrary)]
+//! struct Yakshave {
+//!     #[arbitrary(duration(secs = 60..=3600))]
+//!     took: Duration,
+//!     #[arbitrary(duration)]
+//!     since_last: Duration,
+//! }
+//! ```
+//!
+//! `IpAddr` and `SocketAddr` members may be either version, unless you ask for `ipv4` or `ipv6`:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! use std::net::{IpAddr, SocketAddr};
+//!
+//! #[derive(Clone, Arbitrary)]
+//! struct Yakshave {
+//!     #[arbitrary(ipv4)]
+//!     client: IpAddr,
+//!     #[arbitrary(ipv6)]
+//!     server: SocketAddr,
+//! }
+//! ```
+//!
+//! `char` members can be limited to `ascii`, ASCII `alphanumeric`s, or any `RangeInclusive<char>`s,
+//! rather than any scalar value:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! struct Yak {
+//!     #[arbitrary(char(alphanumeric))]
+//!     initial: char,
+//!     #[arbitrary(char(ranges = ['a'..='f', '0'..='9']))]
+//!     hex: char,
 //! }
 //! ```
 //!
@@ -44,369 +436,4307 @@
 //! }
 //! ```
 //!
-//! You can add bounds for generic structs:
+//! Variants are built with the delimiters they're declared with,
+//! so a unit `Wild`, an empty tuple `Wild()` and an empty struct `Wild {}` each generate (or skip) as themselves.
+//!
+//! `#[deprecated]` variants are skipped too, unless you ask for them:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! #[arbitrary(include_deprecated)]
+//! enum YakType {
+//!     Domestic,
+//!     #[deprecated]
+//!     Extinct,
+//! }
+//! ```
+//!
+//! If only one variant can be generated, it's built directly.
+//! So are fieldless variants, without cloning, though `Arbitrary` itself still requires `Clone`.
+//! With `round_robin` on the item, each call generates the next variant in turn, rather than a random one.
+//! There's a counter for each thread, shared by all of a generic enum's instantiations, which is never reset.
+//! Rust's test harness normally runs each test on its own thread, so each test starts from the first variant.
+//! With `portable` on the item, variants are picked with `u32` draws rather than a `usize`,
+//! so the same random source picks the same variants on 32- and 64-bit targets.
+//! Draws are rejected until one comes from a range that's fair to every variant,
+//! which also skips quickcheck's frequent `0`, `1` and `u32::MAX`,
+//! so variants are picked uniformly, like the default.
+//! `unbiased` picks the same way, for when it's the fairness you're after.
+//! Members are still generated by their own `Arbitrary` impls.
+//! By default, an enum with fields builds every variant and clones out the chosen one.
+//! With `no_clone` on the item, it builds only the chosen variant.
+//! `Arbitrary` still requires `Clone`, but generating never calls it.
+//! With `distinct_variants` on the item, it's an error for two generated variants to have fields
+//! of the same names and types, which catches copy-pasted variants.
+//! Unit variants only differ by name, so they're never compared.
+//!
+//! To check how variants are being picked, `instrument` on the item counts each generated variant.
+//! The counts are read with a generated `arbitrary_variant_counts()`, and cleared with `reset_arbitrary_variant_counts()`,
+//! which are only visible within your crate, and hidden from its docs.
+//! There's one set of counts for each thread, not for each test or `Gen`,
+//! and every instantiation of a generic enum adds to the same counts.
+//! Rust's test harness runs each test on its own thread, so tests running in parallel don't see each other's counts,
+//! but reset them before counting, in case something else on the thread has generated values:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! # use quickcheck::{Arbitrary, Gen};
+//! #[derive(Clone, Arbitrary)]
+//! #[arbitrary(instrument)]
+//! enum Shaver {
+//!     Human,
+//!     Robot(u8),
+//! }
+//!
+//! Shaver::reset_arbitrary_variant_counts();
+//! for _ in 0..100 {
+//!     Shaver::arbitrary(&mut Gen::new(10));
+//! }
+//! let counts = Shaver::arbitrary_variant_counts();
+//! assert_eq!(counts.iter().map(|(_, n)| n).sum::<u64>(), 100);
+//! ```
+//!
+//! A variant with `rare` is generated a tenth as often as each of the others,
+//! e.g. for a catch-all that should only turn up now and then.
+//! That holds with `portable`, `unbiased` and `exclude_if` too,
+//! but `round_robin` generates every variant in turn, so it can't be combined with `rare`:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! enum Frame {
+//!     Data(Vec<u8>),
+//!     Ping,
+//!     #[arbitrary(rare)]
+//!     Unknown(u16),
+//! }
+//! ```
+//!
+//! A variant with `exclude_if = PATH` calls `PATH()` each time a value is generated,
+//! and is left out of that choice if it returns `true`.
+//! If every variant is left out, generation panics:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! fn is_offline() -> bool {
+//!     std::env::var_os("OFFLINE").is_some()
+//! }
+//!
+//! #[derive(Clone, Arbitrary)]
+//! enum Fetch {
+//!     Cached,
+//!     #[arbitrary(exclude_if = is_offline)]
+//!     Download,
+//! }
+//! ```
+//!
+//! [`shrink`](https://docs.rs/quickcheck/latest/quickcheck/trait.Arbitrary.html#method.shrink)
+//! shrinks the fields of a struct, or of the current enum variant.
+//! It never moves to another variant, so a variant marked `#[arbitrary(no_shrink_target)]`
+//! is only ever generated, never shrunk into.
+//! Fields with any `#[arbitrary(...)]` strategy are held constant while shrinking,
+//! except `gen_fn`, which shrinks with its strategy.
+//! That way shrinking can't break e.g. a `gen` that clamps its value.
+//! `#[repr(packed)]` structs don't shrink, because their fields can't be borrowed.
+//! Where smaller values are still valid, `type_shrink` shrinks the field with its type's own `shrink`:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! # use quickcheck::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! struct Yak {
+//!     #[arbitrary(gen(|g| u8::arbitrary(g) % 10 + 1))]
+//!     horns: u8,
+//!     #[arbitrary(gen(|g| u32::arbitrary(g) % 1000), type_shrink)]
+//!     weight: u32,
+//! }
+//! ```
+//!
+//! Free members shrink together as a tuple, which copies the other free members for each candidate.
+//! With `shrink_in_place` on the item, each one shrinks by itself in a copy of the value instead, like `gen_fn` members.
+//! The candidates and their order are the same.
+//! Each candidate then avoids copying the whole tuple, which helps wide items when most candidates are tried, as while quickcheck minimizes a failure.
+//! But it copies the whole value once per free member before the first candidate,
+//! so the tuple is cheaper when only a few candidates are tried and the pinned members are large:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! #[arbitrary(shrink_in_place)]
+//! struct Herd {
+//!     names: Vec<String>,
+//!     ages: Vec<u8>,
+//!     weights: Vec<u32>,
+//! }
+//! ```
+//!
+//! Recursive types usually end in an `Option<Box<Self>>` member, which is `None` more often the deeper it's nested.
+//! Each level sees half the size of the `Gen`, so nesting stops within `log2(g.size())` levels.
+//!
+//! You can add bounds for generic structs.
+//! Generic members need some, and `#[arbitrary(error_if_generic)]` reports a member without any,
+//! before the compiler complains about the impl.
+//! Bounds in the struct's parameter list or `where` clause are kept.
+//! `#[arbitrary(...)]` may be repeated, and bounds accumulate:
 //! ```
 //! # use derive_quickcheck_arbitrary::Arbitrary;
 //! # use quickcheck::Arbitrary;
 //! #[derive(Clone, Arbitrary)]
 //! #[arbitrary(where(T: Arbitrary))]
+//! #[arbitrary(where(U: Arbitrary))]
+//! struct GenericYak<T, U> {
+//!     name: T,
+//!     nickname: U,
+//! }
+//! ```
+//!
+//! Bounds can also live on the member that needs them, alongside its generator:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! # use quickcheck::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
 //! struct GenericYak<T> {
+//!     #[arbitrary(gen(|g| vec![T::arbitrary(g)]), where(T: Arbitrary))]
+//!     names: Vec<T>,
+//! }
+//! ```
+//!
+//! `bound_for` is shorthand for bounding a single parameter:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! # use quickcheck::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! #[arbitrary(bound_for(T = "Arbitrary + Default"))]
+//! struct GenericYak<T, U: Arbitrary> {
 //!     name: T,
+//!     nickname: U,
 //! }
 //! ```
-
-use proc_macro2::{Ident, Span, TokenStream};
-use quote::{quote, ToTokens as _};
-use structmeta::{NameArgs, StructMeta};
-use syn::{
-    parse::{Parse, ParseStream, Parser as _},
-    parse_macro_input,
-    punctuated::Punctuated,
-    spanned::Spanned as _,
-    token::{Brace, Colon, Comma},
-    AttrStyle, Attribute, DataEnum, DataStruct, DeriveInput, Expr, ExprStruct, FieldValue, Fields,
-    Index, Member, Path, PathSegment, Token, Variant, WhereClause, WherePredicate,
-};
-
+//!
+//! To catch broken generators, `validate` checks every generated value in debug builds,
+//! panicking with "generated value failed validation":
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! #[arbitrary(validate(|it: &Self| it.min <= it.max))]
+//! struct Herd {
+//!     #[arbitrary(gen(|_| 1))]
+//!     min: u8,
+//!     #[arbitrary(gen(|_| 10))]
+//!     max: u8,
+//! }
+//! ```
+//!
+//! One field can be generated first, as a seed for the others.
+//! Generators that take a second argument can then read it
+//! (the seed's type can't be generic):
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! #[arbitrary(seed_field = id)]
+//! struct Yak {
+//!     #[arbitrary(gen(|_, built| format!("yak-{}", built.id)))]
+//!     name: String,
+//!     id: u32,
+//! }
+//! ```
+//!
+//! Members are generated in the order they're declared, unless they have an `order`.
+//! Those are generated first, lowest first, which matters for generators with side effects:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! # fn next_id() -> u32 { 0 }
+//! #[derive(Clone, Arbitrary)]
+//! struct Yak {
+//!     #[arbitrary(gen(|_| next_id()))]
+//!     calf_id: u32,
+//!     #[arbitrary(gen(|_| next_id()), order = 0)]
+//!     id: u32,
+//! }
+//! ```
+//!
+//! A struct with a `constructor = PATH` is built by calling `PATH` with its members in declaration order,
+//! rather than with a struct literal.
+//! Members are still generated with their own attributes,
+//! and the constructor must be a `fn` taking exactly the members' types and returning `Self`.
+//! Values aren't shrunk, since rebuilding them would skip the constructor:
+//! ```
+//! # use derive_quickcheck_arbitrary::Arbitrary;
+//! #[derive(Clone, Arbitrary)]
+//! #[arbitrary(constructor = Span::from_parts)]
+//! struct Span {
+//!     #[arbitrary(range(..=100))]
+//!     start: u8,
+//!     end: u8,
+//! }
+//!
+//! impl Span {
+//!     fn from_parts(a: u8, b: u8) -> Self {
+//!         Self { start: a.min(b), end: a.max(b) }
+//!     }
+//! }
+//! ```
+//!
+//! With the `proptest` feature, `#[arbitrary(proptest)]` on the item also implements
+//! [`proptest::arbitrary::Arbitrary`](https://docs.rs/proptest/latest/proptest/arbitrary/trait.Arbitrary.html),
+//! so the type must also be `Debug` and `'static`.
+//! Every value is drawn from `proptest` strategies, so it replays from a `proptest` seed,
+//! and both the variant and its fields shrink.
+//! The variant is chosen as `rare`, `exclude_if` and `skip` say, and never shrinks into a `no_shrink_target` one.
+//! Fields take `default`, `len`, `range`, `where` and `bound_for`,
+//! honour `respect_serde_skip` and `constructor`, and otherwise use their own `proptest` strategy.
+//! The rest of the arguments need a `quickcheck::Gen`, or a second pass over the value,
+//! so aren't supported with `proptest`, and are reported at compile time:
+//! `gen`, `gen_iter`, `gen_fn`, `try_gen`, `byte_len`, `system_time`, `duration`, `ipv4`, `ipv6`, `char`,
+//! `head`, `tail_len`, `repeat_with`, `each`, `distinct`, `validate` and `seed_field`.
+//! Neither are `Option<Box<Self>>` members; write a `prop_recursive` strategy by hand.
+//! Arguments that only choose how `quickcheck` generates, like `size`, `portable` or `shrink_in_place`, are ignored.
+//!
+//! If you need to write `impl Arbitrary` by hand, you can still reuse the per-field generation with
+//! [`arbitrary_fields!`](https://docs.rs/derive-quickcheck-arbitrary/latest/derive_quickcheck_arbitrary/macro.arbitrary_fields.html).
+//! Where you can't put a `#[derive]` on the struct at all,
+//! [`arbitrary_impl!`](https://docs.rs/derive-quickcheck-arbitrary/latest/derive_quickcheck_arbitrary/macro.arbitrary_impl.html)
+//! writes the whole impl from a copy of its fields.
+//! And [`arbitrary_for!`](https://docs.rs/derive-quickcheck-arbitrary/latest/derive_quickcheck_arbitrary/macro.arbitrary_for.html)
+//! writes it from a spec of each field's arguments, for e.g. generated code.
+
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use std::collections::HashSet;
+use structmeta::{NameArgs, NameValue, StructMeta};
+use syn::{
+    ext::IdentExt as _,
+    parse::{Parse, ParseStream, Parser as _},
+    parse_macro_input, parse_quote,
+    punctuated::{Pair, Punctuated},
+    spanned::Spanned as _,
+    token::{Brace, Colon, Comma, Paren},
+    AngleBracketedGenericArguments, AttrStyle, Attribute, DataEnum, DataStruct, DeriveInput, Expr,
+    ExprArray, ExprCall, ExprClosure, ExprLit, ExprPath, ExprStruct, Field, FieldMutability,
+    FieldValue, Fields, GenericArgument, Generics, Index, Lit, LitStr, Member, Path, PathArguments,
+    PathSegment, PredicateType, Token, TraitBound, Type, TypeArray, TypeGroup, TypeParamBound,
+    TypeParen, TypePath, TypeReference, TypeSlice, TypeTraitObject, TypeTuple, Variant,
+    WhereClause, WherePredicate,
+};
+
+#[cfg(feature = "proptest")]
+mod proptest;
+
 // TODO: https://docs.rs/proc-macro-crate/latest/proc_macro_crate/
 // TODO: https://crates.io/crates/parse-variants
 
-#[proc_macro_derive(Arbitrary, attributes(arbitrary))]
-pub fn derive_arbitrary(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let user_struct = parse_macro_input!(input as DeriveInput);
-    expand_arbitrary(user_struct)
-        .unwrap_or_else(syn::Error::into_compile_error)
-        .into()
-}
+// Generated code spells out `::core`/`::std` paths, and calls trait methods as `Trait::method(..)`,
+// so it still compiles where the user's names shadow the prelude (see `tests/shadowed.rs`).
+
+#[proc_macro_derive(Arbitrary, attributes(arbitrary))]
+pub fn derive_arbitrary(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let user_struct = parse_macro_input!(input as DeriveInput);
+    expand_arbitrary(user_struct)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Build a struct using the same generation rules as [`derive@Arbitrary`].
+///
+/// Takes a struct expression and a [`&mut quickcheck::Gen`](https://docs.rs/quickcheck/latest/quickcheck/struct.Gen.html).
+/// Shorthand fields are generated, honouring any `#[arbitrary(...)]` attributes on them.
+/// Everything else is left alone, so you can write the interesting fields yourself.
+/// ```
+/// # use derive_quickcheck_arbitrary::arbitrary_fields;
+/// # use quickcheck::{Arbitrary, Gen};
+/// #[derive(Clone)]
+/// struct Yakshaver {
+///     id: usize,
+///     names: Vec<String>,
+///     even: u8,
+/// }
+///
+/// impl Arbitrary for Yakshaver {
+///     fn arbitrary(g: &mut Gen) -> Self {
+///         arbitrary_fields!(
+///             Self {
+///                 id,
+///                 #[arbitrary(len(1..=3))]
+///                 names,
+///                 even: u8::arbitrary(g) & !1,
+///             },
+///             g
+///         )
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn arbitrary_fields(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as ArbitraryFields);
+    expand_arbitrary_fields(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implement [`quickcheck::Arbitrary`] exactly as [`derive@Arbitrary`] would,
+/// for a struct defined elsewhere.
+///
+/// Takes what the struct definition would be, with `for` instead of `struct`.
+/// The fields must match the real struct's.
+/// ```
+/// # use derive_quickcheck_arbitrary::arbitrary_impl;
+/// mod elsewhere {
+///     #[derive(Clone)]
+///     pub struct Yakshaver<T> {
+///         pub id: usize,
+///         pub names: Vec<T>,
+///     }
+/// }
+/// use elsewhere::Yakshaver;
+///
+/// arbitrary_impl!(
+///     #[arbitrary(where(T: quickcheck::Arbitrary))]
+///     for Yakshaver<T> {
+///         id: usize,
+///         #[arbitrary(len(1..=3))]
+///         names: Vec<T>,
+///     }
+/// );
+/// ```
+#[proc_macro]
+pub fn arbitrary_impl(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as ArbitraryImpl);
+    expand_arbitrary(input.0)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implement [`quickcheck::Arbitrary`] for a struct from a separate spec,
+/// without touching its definition.
+///
+/// Takes the struct's name and generic parameters, then each of its fields in braces,
+/// with the arguments that would be in its `#[arbitrary(...)]` attribute.
+/// Several arguments go in parentheses, and fields without any are generated as usual.
+/// Every field must be listed, and tuple struct fields are listed by index, in order.
+/// Item-level arguments go in attributes before the name.
+/// ```
+/// # use derive_quickcheck_arbitrary::arbitrary_for;
+/// mod generated {
+///     #[derive(Clone)]
+///     pub struct Yakshaver<T> {
+///         pub id: usize,
+///         pub names: Vec<T>,
+///         pub nickname: String,
+///     }
+/// }
+/// use generated::Yakshaver;
+///
+/// arbitrary_for!(
+///     #[arbitrary(where(T: quickcheck::Arbitrary + PartialEq))]
+///     Yakshaver<T>,
+///     {
+///         id: gen(|g| g.size()),
+///         names: (len(1..=3), distinct),
+///         nickname,
+///     }
+/// );
+/// ```
+///
+/// The field types aren't known, so nothing that depends on them applies,
+/// and there's no `proptest` impl.
+#[proc_macro]
+pub fn arbitrary_for(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as ArbitraryFor);
+    expand_impls(input.0, false)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A struct's name and generics, and a spec for each of its fields.
+struct ArbitraryFor(DeriveInput);
+
+impl Parse for ArbitraryFor {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let ident = input.parse::<Ident>()?;
+        let generics = input.parse::<Generics>()?;
+        input.parse::<Comma>()?;
+        let content;
+        let brace_token = syn::braced!(content in input);
+        input.parse::<Option<Comma>>()?;
+        let specs = content.parse_terminated(FieldSpec::parse, Comma)?;
+        let unnamed = specs
+            .iter()
+            .all(|it| matches!(it.member, Member::Unnamed(_)));
+        let fields = specs
+            .into_iter()
+            .enumerate()
+            .map(|(ix, FieldSpec { member, attrs })| {
+                // point errors at the spec
+                let ty = Type::Infer(syn::TypeInfer {
+                    underscore_token: Token![_](member.span()),
+                });
+                let ident = match (member, unnamed) {
+                    (Member::Named(ident), false) => Some(ident),
+                    (Member::Unnamed(index), true) if index.index as usize == ix => None,
+                    (member, _) => {
+                        return Err(syn::Error::new_spanned(
+                            member,
+                            "list either named fields, or every tuple field by index, in order",
+                        ))
+                    }
+                };
+                Ok(Field {
+                    attrs,
+                    vis: syn::Visibility::Inherited,
+                    mutability: FieldMutability::None,
+                    ident,
+                    colon_token: None,
+                    ty,
+                })
+            })
+            .collect::<syn::Result<Punctuated<_, Comma>>>()?;
+        let fields = match unnamed {
+            true => Fields::Unnamed(syn::FieldsUnnamed {
+                paren_token: Default::default(),
+                unnamed: fields,
+            }),
+            false => Fields::Named(syn::FieldsNamed {
+                brace_token,
+                named: fields,
+            }),
+        };
+        Ok(Self(DeriveInput {
+            attrs,
+            vis: syn::Visibility::Inherited,
+            ident,
+            generics,
+            data: syn::Data::Struct(DataStruct {
+                struct_token: Default::default(),
+                fields,
+                semi_token: None,
+            }),
+        }))
+    }
+}
+
+/// `member`, or `member: ARG`, or `member: (ARG, ...)` in [`ArbitraryFor`].
+struct FieldSpec {
+    member: Member,
+    /// The equivalent `#[arbitrary(...)]`, if any.
+    attrs: Vec<Attribute>,
+}
+
+impl Parse for FieldSpec {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let member = input.parse::<Member>()?;
+        if input.parse::<Option<Colon>>()?.is_none() {
+            return Ok(Self {
+                member,
+                attrs: vec![],
+            });
+        }
+        let args = match input.peek(syn::token::Paren) {
+            true => {
+                let content;
+                syn::parenthesized!(content in input);
+                content.parse::<TokenStream>()?
+            }
+            // up to the next field
+            false => {
+                let mut args = TokenStream::new();
+                while !input.is_empty() && !input.peek(Comma) {
+                    args.extend([input.parse::<TokenTree>()?]);
+                }
+                args
+            }
+        };
+        Ok(Self {
+            member,
+            attrs: vec![parse_quote!(#[arbitrary(#args)])],
+        })
+    }
+}
+
+/// A struct definition, spelled with `for` instead of `struct`.
+struct ArbitraryImpl(DeriveInput);
+
+impl Parse for ArbitraryImpl {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let for_token = input.parse::<Token![for]>()?;
+        let rest = input.parse::<TokenStream>()?;
+        let struct_token = Token![struct](for_token.span);
+        syn::parse2(quote!(#(#attrs)* #struct_token #rest)).map(Self)
+    }
+}
+
+struct ArbitraryFields {
+    expr: ExprStruct,
+    gen_name: Ident,
+}
+
+impl Parse for ArbitraryFields {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let expr = input.parse()?;
+        input.parse::<Comma>()?;
+        let gen_name = input.parse()?;
+        input.parse::<Option<Comma>>()?;
+        Ok(Self { expr, gen_name })
+    }
+}
+
+fn expand_arbitrary_fields(
+    ArbitraryFields { mut expr, gen_name }: ArbitraryFields,
+) -> syn::Result<TokenStream> {
+    let gen_name = &gen_name.into_token_stream();
+    let parent = &type_name(&Type::Path(TypePath {
+        qself: None,
+        path: expr.path.clone(),
+    }));
+    let mut predicates = Punctuated::new();
+    let cx = &mut Context {
+        gen_name,
+        predicates: &mut predicates,
+        finite_floats: false,
+        seeded: false,
+        respect_serde_skip: false,
+        item: None,
+        recursive: false,
+        with_gen: None,
+    };
+    expr.fields = expr
+        .fields
+        .into_pairs()
+        .enumerate()
+        .map(|(ix, pair)| {
+            let (field_value, punct) = pair.into_tuple();
+            let field_value = match field_value {
+                // shorthand
+                FieldValue {
+                    attrs,
+                    member: Member::Named(ident),
+                    colon_token: None,
+                    expr: _,
+                } if get_args(&attrs)?.order.is_some() => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "`order` is not valid in `arbitrary_fields!`, which generates fields as written",
+                    ))
+                }
+                FieldValue {
+                    attrs,
+                    member: Member::Named(ident),
+                    colon_token: None,
+                    expr: _,
+                } => self::field_value(
+                    ix,
+                    Field {
+                        attrs,
+                        vis: syn::Visibility::Inherited,
+                        mutability: FieldMutability::None,
+                        ident: Some(ident),
+                        colon_token: None,
+                        ty: Type::Infer(parse_quote!(_)),
+                    },
+                    parent,
+                    cx,
+                )?,
+                explicit => {
+                    if let Some(attr) = explicit
+                        .attrs
+                        .iter()
+                        .find(|it| it.path().is_ident("arbitrary"))
+                    {
+                        return Err(syn::Error::new_spanned(
+                            attr,
+                            "`#[arbitrary(...)]` is only valid on shorthand fields",
+                        ));
+                    }
+                    explicit
+                }
+            };
+            Ok(Pair::new(field_value, punct))
+        })
+        .collect::<syn::Result<_>>()?;
+    match predicates.is_empty() {
+        true => Ok(expr.into_token_stream()),
+        false => Err(syn::Error::new_spanned(
+            predicates,
+            "`where` is not valid in `arbitrary_fields!`",
+        )),
+    }
+}
+
+fn expand_arbitrary(input: DeriveInput) -> syn::Result<TokenStream> {
+    expand_impls(input, true)
+}
+
+/// Other backends need the members' types, so are only implemented if `typed`.
+fn expand_impls(input: DeriveInput, typed: bool) -> syn::Result<TokenStream> {
+    let struct_name = input.ident.clone();
+    let generics = input.generics.clone();
+    let gen_name = &quote!(g);
+    let arg = get_args(&input.attrs)?;
+    check_location(&arg, Location::Item)?;
+    if let Some(name) = arg.names.iter().find(|it| *it == "proptest") {
+        if !cfg!(feature = "proptest") {
+            return Err(syn::Error::new(
+                name.span(),
+                "`proptest` requires this crate's `proptest` feature",
+            ));
+        }
+        if !typed {
+            return Err(syn::Error::new(
+                name.span(),
+                "`proptest` isn't supported by `arbitrary_for!`, which doesn't know the field types",
+            ));
+        }
+        #[cfg(feature = "proptest")]
+        proptest::check_supported(&arg)?;
+    }
+    let Arg {
+        r#where,
+        include_deprecated,
+        finite_floats,
+        validate,
+        seed_field,
+        respect_serde_skip,
+        portable,
+        round_robin,
+        unbiased,
+        no_clone,
+        distinct_variants,
+        instrument,
+        shrink_in_place,
+        with_gen,
+        size,
+        constructor,
+        proptest: derive_proptest,
+        error_if_generic,
+        ..
+    } = arg;
+    // the item's own, then ours
+    let mut predicates = generics
+        .where_clause
+        .clone()
+        .map(|it| it.predicates)
+        .unwrap_or_default();
+    predicates.extend(r#where.into_iter().flatten());
+    // before the inferred `quickcheck::Arbitrary` bounds
+    #[cfg(feature = "proptest")]
+    let item = proptest::Item {
+        name: &struct_name,
+        generics: &generics,
+        predicates: predicates.clone(),
+        allow_deprecated: include_deprecated,
+        respect_serde_skip,
+        constructor: constructor.clone(),
+        is_enum: matches!(input.data, syn::Data::Enum(_)),
+    };
+    let cx = &mut Context {
+        gen_name,
+        predicates: &mut predicates,
+        finite_floats,
+        seeded: false,
+        respect_serde_skip,
+        item: Some(&struct_name),
+        recursive: false,
+        with_gen: with_gen.as_ref(),
+    };
+
+    let mut alternatives = vec![];
+    // (name, path) of each generated variant, with `instrument`
+    let mut counted = None;
+    let (ctor, shrink) = match input.data {
+        syn::Data::Struct(DataStruct { fields, .. }) => {
+            if round_robin || unbiased || no_clone || distinct_variants || instrument {
+                return Err(syn::Error::new(
+                    struct_name.span(),
+                    "`round_robin`, `unbiased`, `no_clone`, `distinct_variants` and `instrument` are only valid for enums",
+                ));
+            }
+            let path = path_of_idents([struct_name.clone()]);
+            let mut members = shrink_members(&fields, respect_serde_skip)?;
+            if constructor.is_some() {
+                // rebuilding a shrunk value would bypass the constructor
+                for (_, shrink) in &mut members {
+                    *shrink = Shrink::Pinned;
+                }
+            }
+            let shrink = match is_packed(&input.attrs) {
+                // can't borrow the fields
+                true => None,
+                false => {
+                    let arm = shrink_arm(&path, &members, shrink_in_place);
+                    Some(quote!(match self { #arm }))
+                }
+            };
+            alternatives.push(Alternative::new(&path, &fields, &members));
+            let seed = seed_field
+                .map(
+                    |name| match fields.iter().find(|it| it.ident.as_ref() == Some(&name)) {
+                        Some(field) => Ok((name, field.ty.clone())),
+                        None => Err(syn::Error::new(
+                            name.span(),
+                            format!("no field named `{}` for `seed_field`", name),
+                        )),
+                    },
+                )
+                .transpose()?;
+            cx.seeded = seed.is_some();
+            let orders = field_orders(&fields)?;
+            let mut values = field_values(&fields, &struct_name.to_string(), cx)?;
+            // the constructor takes the members positionally, like a tuple struct
+            let (path, shape) = match &constructor {
+                Some(_) => (
+                    path_of_idents([Ident::new("construct", Span::call_site())]),
+                    Fields::Unnamed(syn::FieldsUnnamed {
+                        paren_token: Default::default(),
+                        unnamed: fields.iter().cloned().collect(),
+                    }),
+                ),
+                None => (path, fields.clone()),
+            };
+            let ctor = match seed {
+                None => ordered_struct(path, &shape, values, &orders),
+                // generate the seed first, and let the other fields see it
+                Some((name, ty)) => {
+                    let seed = values
+                        .iter_mut()
+                        .find(|it| matches!(&it.member, Member::Named(it) if *it == name))
+                        .expect("seed field exists");
+                    let seed = std::mem::replace(&mut seed.expr, parse_quote!(seed));
+                    let ctor = ordered_struct(path, &shape, values, &orders);
+                    quote! {{
+                        struct __Built {
+                            #name: #ty,
+                        }
+                        let seed: #ty = #seed;
+                        let built = __Built {
+                            #name: ::core::clone::Clone::clone(&seed),
+                        };
+                        #ctor
+                    }}
+                }
+            };
+            let ctor = match constructor {
+                // a wrong signature is reported here, rather than at the call
+                Some(constructor) => {
+                    let tys = fields.iter().map(|it| &it.ty);
+                    quote! {{
+                        let construct: fn(#(#tys),*) -> Self = #constructor;
+                        #ctor
+                    }}
+                }
+                None => ctor,
+            };
+            (ctor, shrink)
+        }
+        syn::Data::Enum(DataEnum { variants, .. }) => {
+            if let Some(name) = seed_field {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "`seed_field` is only valid for structs",
+                ));
+            }
+            if let Some(constructor) = constructor {
+                return Err(syn::Error::new_spanned(
+                    constructor,
+                    "`constructor` is only valid for structs",
+                ));
+            }
+            let mut variant_ctors = vec![];
+            // each generated variant's `exclude_if`
+            let mut exclusions = vec![];
+            // whether each generated variant is `rare`
+            let mut rare = vec![];
+            // (path, free members) for each generated variant
+            let mut generated = vec![];
+            let mut not_generated = vec![];
+            // (name, fields) of each generated variant, for `distinct_variants`
+            let mut shapes = Vec::<(Ident, Fields)>::new();
+            for Variant {
+                attrs,
+                ident,
+                fields,
+                ..
+            } in variants
+            {
+                let arg = get_args(&attrs)?;
+                // TODO: others probably could be
+                check_location(&arg, Location::Variant)?;
+                if arg.rare && round_robin {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        "`rare` can't be combined with `round_robin`, which generates every variant in turn",
+                    ));
+                }
+                let path = path_of_idents([struct_name.clone(), ident.clone()]);
+                // don't generate deprecated variants unless asked
+                if arg.skip || (!include_deprecated && is_deprecated(&attrs)) {
+                    not_generated.push(path);
+                    continue;
+                }
+                if distinct_variants && !fields.is_empty() {
+                    if let Some((other, _)) = shapes.iter().find(|(_, it)| same_shape(it, &fields))
+                    {
+                        return Err(syn::Error::new(
+                            ident.span(),
+                            format!(
+                                "`{}` has the same fields as `{}`, which `distinct_variants` forbids",
+                                ident, other
+                            ),
+                        ));
+                    }
+                    shapes.push((ident.clone(), fields.clone()));
+                }
+                let members = shrink_members(&fields, respect_serde_skip)?;
+                alternatives.push(Alternative {
+                    rare: arg.rare,
+                    exclude_if: arg.exclude_if.clone(),
+                    shrink_target: !arg.no_shrink_target,
+                    ..Alternative::new(&path, &fields, &members)
+                });
+                generated.push((path.clone(), members));
+                let parent = format!("{}::{}", struct_name, ident);
+                let orders = field_orders(&fields)?;
+                variant_ctors.push(ordered_struct(
+                    path,
+                    &fields,
+                    field_values(&fields, &parent, cx)?,
+                    &orders,
+                ));
+                exclusions.push(arg.exclude_if);
+                rare.push(arg.rare);
+            }
+            // each variant shrinks within itself, so nothing shrinks into a
+            // `no_shrink_target` variant
+            let arms = generated
+                .iter()
+                .map(|(path, members)| shrink_arm(path, members, shrink_in_place));
+            let shrink = match generated.is_empty() && not_generated.is_empty() {
+                // can't `match` on a reference to an empty enum
+                true => None,
+                false => Some(quote! {
+                    match self {
+                        #(#arms)*
+                        #(
+                            // might be deprecated
+                            #[allow(deprecated)]
+                            #not_generated { .. } => ::quickcheck::empty_shrinker(),
+                        )*
+                    }
+                }),
+            };
+            if instrument {
+                counted = Some(
+                    generated
+                        .iter()
+                        .map(|(path, _)| (path.segments[1].ident.to_string(), path.clone()))
+                        .collect::<Vec<_>>(),
+                );
+            }
+            let fieldless = generated.iter().all(|(_, members)| members.is_empty());
+            let n = variant_ctors.len();
+            let weighted = rare.contains(&true);
+            let weights = rare
+                .iter()
+                .map(|rare| match (weighted, rare) {
+                    (true, false) => COMMON_WEIGHT,
+                    _ => 1,
+                })
+                .collect::<Vec<_>>();
+            // each index as many times as its weight
+            let table = weights
+                .iter()
+                .enumerate()
+                .flat_map(|(ix, weight)| std::iter::repeat(ix).take(*weight))
+                .collect::<Vec<_>>();
+            let m = table.len();
+            // an index into `variant_ctors`, or `choose` for a `usize` draw
+            let ix = match (round_robin, unbiased, portable) {
+                (true, _, _) => Some(quote!({
+                    ::std::thread_local! {
+                        static NEXT: ::core::cell::Cell<::core::primitive::usize> = ::core::cell::Cell::new(0);
+                    }
+                    NEXT.with(|next| {
+                        let ix = next.get();
+                        next.set((ix + 1) % #n);
+                        ix
+                    })
+                })),
+                (false, true, _) | (false, _, true) => Some(unbiased_index(m, gen_name)),
+                (false, false, false) if weighted => Some(
+                    quote!(*#gen_name.choose(&[ #(#table,)* ]).expect("no variants to choose from")),
+                ),
+                (false, false, false) => None,
+            };
+            // from a draw into the table to the variant it picks
+            let ix = match (weighted, unbiased || portable) {
+                (true, true) => ix.map(|ix| quote!([ #(#table,)* ][#ix])),
+                _ => ix,
+            };
+            match variant_ctors.as_slice() {
+                // the choice is made at generation time
+                _ if exclusions.iter().any(Option::is_some) => (
+                    excluding_ctor(
+                        &struct_name,
+                        &variant_ctors,
+                        &exclusions,
+                        &weights,
+                        (round_robin, unbiased, portable),
+                        gen_name,
+                    ),
+                    shrink,
+                ),
+                // no need to choose - just build it directly
+                [only_ctor] => (only_ctor.into_token_stream(), shrink),
+                // pick an index and build just that variant, without cloning
+                _ if fieldless || no_clone => {
+                    let indices = (0..n).collect::<Vec<_>>();
+                    let ix = ix.unwrap_or_else(|| {
+                        quote!(*#gen_name.choose(&[ #(#indices,)* ]).expect("no variants to choose from"))
+                    });
+                    (
+                        quote!(
+                            match #ix {
+                                #(#indices => #variant_ctors,)*
+                                _ => ::core::unreachable!(),
+                            }
+                        ),
+                        shrink,
+                    )
+                }
+                _ => (
+                    match ix {
+                        Some(ix) => quote!(
+                            let options = [ #(#variant_ctors,)* ];
+                            ::core::clone::Clone::clone(&options[#ix])
+                        ),
+                        None => quote!(
+                            let options = [ #(#variant_ctors,)* ];
+                            ::core::clone::Clone::clone(
+                                #gen_name.choose(options.as_slice()).expect("no variants to choose from")
+                            )
+                        ),
+                    },
+                    shrink,
+                ),
+            }
+        }
+        syn::Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "#[derive(Arbitrary)] is not supported on `union`s",
+            ))
+        }
+    };
+    // shared by every member, so e.g. each branch of a tree counts towards the same depth
+    let depth = cx.recursive.then(|| {
+        quote! {
+            ::std::thread_local! {
+                static __RECURSION_DEPTH: ::core::cell::Cell<::core::primitive::u32> = ::core::cell::Cell::new(0);
+            }
+        }
+    });
+    let predicates = dedup_predicates(predicates);
+    if error_if_generic {
+        check_bounded(&generics, &predicates, &alternatives)?;
+    }
+
+    let allow_deprecated = include_deprecated.then(|| quote!(#[allow(deprecated)]));
+    let (count, counters) = match counted {
+        Some(counted) => instrumentation(&struct_name, &generics, &counted),
+        None => (None, None),
+    };
+    // instead of the caller's `Gen`
+    let size = size.map(|size| {
+        quote! {
+            let _ = #gen_name;
+            let #gen_name = &mut ::quickcheck::Gen::new(#size);
+        }
+    });
+    #[cfg(feature = "proptest")]
+    let proptest = match derive_proptest {
+        true => proptest::expand(item, alternatives)?,
+        false => TokenStream::new(),
+    };
+    #[cfg(not(feature = "proptest"))]
+    let proptest = {
+        // rejected above
+        let _ = derive_proptest;
+        TokenStream::new()
+    };
+    let where_clause = WhereClause {
+        where_token: Token![where](Span::call_site()),
+        predicates,
+    };
+    let shrink = shrink.map(|body| {
+        quote! {
+            #allow_deprecated
+            fn shrink(&self) -> ::std::boxed::Box<dyn ::core::iter::Iterator<Item = Self>> {
+                #body
+            }
+        }
+    });
+
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::quickcheck::Arbitrary for #struct_name #ty_generics
+            #where_clause
+        {
+            #allow_deprecated
+            fn arbitrary(#gen_name: &mut ::quickcheck::Gen) -> Self {
+                #size
+                #depth
+                let value: Self = { #ctor };
+                #count
+                #(
+                    ::core::debug_assert!(
+                        (( #validate ) as fn(&Self) -> ::core::primitive::bool)(&value),
+                        "generated value failed validation"
+                    );
+                )*
+                value
+            }
+            #shrink
+        }
+        #counters
+        #proptest
+    })
+}
+
+/// Count each generated variant of `value` (if `instrument` is on),
+/// and the inherent fns to read and reset the counts.
+///
+/// The counts are per thread, and shared by every instance of a generic enum.
+fn instrumentation(
+    enum_name: &Ident,
+    generics: &Generics,
+    counted: &[(String, Path)],
+) -> (Option<TokenStream>, Option<TokenStream>) {
+    let n = counted.len();
+    let names = counted.iter().map(|(name, _)| name);
+    let arms = counted
+        .iter()
+        .enumerate()
+        .map(|(ix, (_, path))| quote!(#path { .. } => #ix,));
+    let count = quote! {
+        Self::__arbitrary_variant_counters().with(|counts| {
+            // might be deprecated
+            #[allow(deprecated, unreachable_patterns)]
+            let ix = match &value {
+                #(#arms)*
+                _ => ::core::unreachable!("skipped variants aren't generated"),
+            };
+            counts.borrow_mut()[ix] += 1;
+        });
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let counters = quote! {
+        #[automatically_derived]
+        impl #impl_generics #enum_name #ty_generics #where_clause {
+            #[doc(hidden)]
+            fn __arbitrary_variant_counters(
+            ) -> &'static ::std::thread::LocalKey<::core::cell::RefCell<[::core::primitive::u64; #n]>> {
+                ::std::thread_local! {
+                    static COUNTS: ::core::cell::RefCell<[::core::primitive::u64; #n]> =
+                        ::core::cell::RefCell::new([0; #n]);
+                }
+                &COUNTS
+            }
+
+            /// How many times each variant has been generated on this thread,
+            /// since the last [`reset_arbitrary_variant_counts`](Self::reset_arbitrary_variant_counts).
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            pub(crate) fn arbitrary_variant_counts(
+            ) -> ::std::vec::Vec<(&'static ::core::primitive::str, ::core::primitive::u64)> {
+                let names: [&'static ::core::primitive::str; #n] = [#(#names),*];
+                Self::__arbitrary_variant_counters().with(|counts| {
+                    ::core::iter::Iterator::collect(::core::iter::Iterator::zip(
+                        ::core::iter::IntoIterator::into_iter(names),
+                        *counts.borrow(),
+                    ))
+                })
+            }
+
+            /// Set every count from [`arbitrary_variant_counts`](Self::arbitrary_variant_counts) back to `0`.
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            pub(crate) fn reset_arbitrary_variant_counts() {
+                Self::__arbitrary_variant_counters().with(|counts| *counts.borrow_mut() = [0; #n]);
+            }
+        }
+    };
+    (Some(count), Some(counters))
+}
+
+/// A uniform index below `n`, from `u32` draws.
+///
+/// Draws are rejected unless they're in `n..limit`, which holds the same number of
+/// values for each remainder, and leaves out quickcheck's favourite `0`, `1` and `u32::MAX`.
+fn unbiased_index(n: usize, gen_name: &TokenStream) -> TokenStream {
+    let limit = match u32::MAX.checked_div(n as u32) {
+        Some(per_index) if n >= 2 => per_index * n as u32,
+        _ => return quote!(::core::panic!("no variants to choose from")),
+    };
+    let n = n as u32;
+    quote!(loop {
+        let draw = <::core::primitive::u32 as ::quickcheck::Arbitrary>::arbitrary(#gen_name);
+        if (#n..#limit).contains(&draw) {
+            break (draw % #n) as ::core::primitive::usize;
+        }
+    })
+}
+
+/// Choose between `ctors`, leaving out those whose `exclude_if` returns `true` for this call.
+///
+/// `(round_robin, unbiased, portable)` pick from the remaining variants as they would from all of them,
+/// and each one is as likely as its weight.
+fn excluding_ctor(
+    enum_name: &Ident,
+    ctors: &[TokenStream],
+    exclusions: &[Option<Path>],
+    weights: &[usize],
+    (round_robin, unbiased, portable): (bool, bool, bool),
+    gen_name: &TokenStream,
+) -> TokenStream {
+    let indices = (0..ctors.len()).collect::<Vec<_>>();
+    let included =
+        exclusions
+            .iter()
+            .zip(&indices)
+            .zip(weights)
+            .map(|((exclude_if, ix), weight)| {
+                let include = match weight {
+                    1 => quote!(options.push(#ix);),
+                    _ => quote!(::core::iter::Extend::extend(&mut options, [#ix; #weight]);),
+                };
+                match exclude_if {
+                    Some(path) => quote! {
+                        if !#path() {
+                            #include
+                        }
+                    },
+                    None => include,
+                }
+            });
+    let exhausted = format!("every variant of `{}` is excluded", enum_name);
+    let chosen = match (round_robin, unbiased, portable) {
+        (true, _, _) => quote!({
+            ::std::thread_local! {
+                static NEXT: ::core::cell::Cell<::core::primitive::usize> = ::core::cell::Cell::new(0);
+            }
+            NEXT.with(|next| {
+                let ix = next.get() % options.len();
+                next.set(ix + 1);
+                options[ix]
+            })
+        }),
+        (false, true, _) | (false, _, true) => quote!({
+            let n = options.len() as ::core::primitive::u32;
+            let limit = (::core::primitive::u32::MAX / n) * n;
+            loop {
+                let draw = <::core::primitive::u32 as ::quickcheck::Arbitrary>::arbitrary(#gen_name);
+                if (n..limit).contains(&draw) {
+                    break options[(draw % n) as ::core::primitive::usize];
+                }
+            }
+        }),
+        (false, false, false) => quote!(*#gen_name.choose(&options).expect(#exhausted)),
+    };
+    quote! {{
+        let mut options = ::std::vec::Vec::<::core::primitive::usize>::new();
+        #(#included)*
+        ::core::assert!(!options.is_empty(), "{}", #exhausted);
+        match #chosen {
+            #(#indices => #ctors,)*
+            _ => ::core::unreachable!(),
+        }
+    }}
+}
+
+/// A struct or enum variant that can be generated, for other backends.
+#[cfg_attr(not(feature = "proptest"), allow(dead_code))]
+struct Alternative {
+    path: Path,
+    /// Each field, its member, and whether it's free to shrink.
+    fields: Vec<(Field, Member, bool)>,
+    /// Generated less often than the others.
+    rare: bool,
+    /// A `fn() -> bool` that excludes it when it returns `true`.
+    exclude_if: Option<Path>,
+    /// Whether another variant may shrink into it.
+    shrink_target: bool,
+}
+
+impl Alternative {
+    fn new(path: &Path, fields: &Fields, members: &[(Member, Shrink)]) -> Self {
+        let item = &path.segments[0].ident;
+        Self {
+            path: path.clone(),
+            fields: fields
+                .iter()
+                .zip(members)
+                .map(|(field, (member, shrink))| {
+                    (
+                        field.clone(),
+                        member.clone(),
+                        // bounding on `Option<Box<Self>>` would be cyclic
+                        matches!(shrink, Shrink::Free) && !is_recursive_option(&field.ty, item),
+                    )
+                })
+                .collect(),
+            rare: false,
+            exclude_if: None,
+            shrink_target: true,
+        }
+    }
+}
+
+/// Catch generic members with no bounds at all, before the compiler
+/// complains about `Clone` and `'static` on the impl.
+///
+/// Bounds in the parameter list count, as do all the `predicates`.
+fn check_bounded(
+    generics: &Generics,
+    predicates: &Punctuated<WherePredicate, Comma>,
+    alternatives: &[Alternative],
+) -> syn::Result<()> {
+    let bounded_types = predicates
+        .iter()
+        .filter_map(|it| match it {
+            WherePredicate::Type(PredicateType { bounded_ty, .. }) => Some(bounded_ty),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let unbounded = generics
+        .type_params()
+        .filter(|it| it.bounds.is_empty())
+        .map(|it| &it.ident)
+        .filter(|param| !bounded_types.iter().any(|ty| mentions(ty, param)))
+        .collect::<Vec<_>>();
+    for Alternative { path, fields, .. } in alternatives {
+        for (Field { ty, .. }, member, _) in fields {
+            if let Some(param) = unbounded.iter().find(|param| mentions(ty, param)) {
+                let path = path.to_token_stream().to_string().replace(" :: ", "::");
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    format!(
+                        "`{}.{}: {}` uses `{}`, which has no bounds; add them with e.g. `#[arbitrary(where({}: Arbitrary))]`",
+                        path,
+                        member.to_token_stream(),
+                        type_name(ty),
+                        param,
+                        param,
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Drop repeated predicates, like the `T: Arbitrary` that each `Cell<T>` member adds,
+/// keeping the first of each.
+fn dedup_predicates(
+    predicates: Punctuated<WherePredicate, Comma>,
+) -> Punctuated<WherePredicate, Comma> {
+    let mut seen = HashSet::new();
+    predicates
+        .into_iter()
+        .filter(|it| seen.insert(it.to_token_stream().to_string()))
+        .collect()
+}
+
+/// Whether `ident` appears anywhere in `ty`.
+fn mentions(ty: &Type, ident: &Ident) -> bool {
+    fn walk(tokens: TokenStream, ident: &Ident) -> bool {
+        tokens.into_iter().any(|tt| match tt {
+            TokenTree::Ident(it) => it == *ident,
+            TokenTree::Group(group) => walk(group.stream(), ident),
+            _ => false,
+        })
+    }
+    walk(ty.to_token_stream(), ident)
+}
+
+/// Item-level state shared by all members.
+struct Context<'a> {
+    gen_name: &'a TokenStream,
+    /// Member-level `where` predicates are collected here.
+    predicates: &'a mut Punctuated<WherePredicate, Comma>,
+    finite_floats: bool,
+    /// Whether there's a `seed_field` for generators to see.
+    seeded: bool,
+    /// Treat `#[serde(skip)]` members as `#[arbitrary(default)]`.
+    respect_serde_skip: bool,
+    /// The item being derived for, to spot `Option<Box<Self>>` members.
+    item: Option<&'a Ident>,
+    /// Whether any member reads the item's recursion depth.
+    recursive: bool,
+    /// What generators take instead of the `Gen`.
+    with_gen: Option<&'a Type>,
+}
+
+/// `parent` names the struct or variant in diagnostics.
+fn field_values(
+    fields: &Fields,
+    parent: &str,
+    cx: &mut Context,
+) -> syn::Result<Punctuated<FieldValue, Comma>> {
+    fields
+        .iter()
+        .cloned()
+        .enumerate()
+        .map(|(ix, field)| field_value(ix, field, parent, cx))
+        .collect()
+}
+
+fn field_value(ix: usize, field: Field, parent: &str, cx: &mut Context) -> syn::Result<FieldValue> {
+    let gen_name = cx.gen_name;
+    let arg = get_args(&field.attrs)?;
+    check_location(&arg, Location::Member)?;
+    let Arg {
+        value,
+        r#where,
+        distinct,
+        finite,
+        flatten,
+        type_shrink,
+        ..
+    } = arg;
+    if let Some(Value::GenIter(custom)) = &value {
+        if takes_built(custom) {
+            return Err(syn::Error::new_spanned(
+                custom,
+                "`gen_iter` generators don't take the built fields",
+            ));
+        }
+    }
+    if let Some(
+        Value::Gen(custom)
+        | Value::TryGen { gen: custom, .. }
+        | Value::HeadTail { head: custom, .. }
+        | Value::RepeatWith { gen: custom, .. },
+    ) = &value
+    {
+        if takes_built(custom) && !cx.seeded {
+            return Err(syn::Error::new_spanned(
+                custom,
+                "generators only take the built fields with an item-level `seed_field`",
+            ));
+        }
+    }
+    if finite && (value.is_some() || distinct) {
+        return Err(excludes_values(&field, "finite", &["distinct"]));
+    }
+    if flatten && (value.is_some() || distinct || finite) {
+        return Err(excludes_values(&field, "flatten", &["distinct", "finite"]));
+    }
+    if matches!(value, Some(Value::Range(_))) && !is_type_named(&field.ty, INTS) {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "`range` is only valid for integer members of up to 64 bits",
+        ));
+    }
+    if matches!(value, Some(Value::Ip { .. }))
+        && !is_type_named(&field.ty, &["IpAddr", "SocketAddr"])
+    {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "`ipv4` and `ipv6` are only valid for `IpAddr` and `SocketAddr` members",
+        ));
+    }
+    if type_shrink && matches!(value, None | Some(Value::Strategy(_))) {
+        return Err(syn::Error::new_spanned(
+            field,
+            "`type_shrink` is only valid for members with a strategy that would hold them, other than `gen_fn`",
+        ));
+    }
+    if matches!(value, Some(Value::ByteLen(_))) && !is_type_named(&field.ty, &["String"]) {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "`byte_len` is only valid for `String` members",
+        ));
+    }
+    if matches!(value, Some(Value::Char(_))) && !is_type_named(&field.ty, &["char"]) {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "`char` is only valid for `char` members",
+        ));
+    }
+    if matches!(value, Some(Value::Duration { .. }))
+        && !matches!(field.ty, Type::Infer(_))
+        && !is_type_named(&field.ty, &["Duration"])
+    {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "`duration` is only valid for `Duration` members",
+        ));
+    }
+    if finite && !is_float(&field.ty) {
+        return Err(syn::Error::new_spanned(
+            &field.ty,
+            "`finite` is only valid for `f32` and `f64` members",
+        ));
+    }
+    // our own strategy wins
+    let value = match value {
+        None if cx.respect_serde_skip
+            && !distinct
+            && !finite
+            && !flatten
+            && is_serde_skipped(&field.attrs) =>
+        {
+            Some(Value::Default)
+        }
+        value => value,
+    };
+    let finite = finite
+        || (cx.finite_floats && value.is_none() && !distinct && !flatten && is_float(&field.ty));
+    cx.predicates.extend(r#where.into_iter().flatten());
+    // collected, or from `quickcheck`'s impl, either of which needs the hasher
+    if let (
+        Some((_, _, hasher)),
+        None
+        | Some(Value::Len(_) | Value::GenIter(_) | Value::HeadTail { .. } | Value::RepeatWith { .. }),
+    ) = (hashed_collection(&field.ty), &value)
+    {
+        cx.predicates.push(parse_quote! {
+            #hasher: ::std::hash::BuildHasher + ::core::default::Default + ::core::clone::Clone + 'static
+        });
+    }
+    let name = match &field.ident {
+        Some(ident) => ident.to_string(),
+        None => ix.to_string(),
+    };
+    // for runtime panics
+    let label = match &field.ty {
+        Type::Infer(_) => format!("{}.{}", parent, name),
+        ty => format!("{}.{}: {}", parent, name, type_name(ty)),
+    };
+    let value = match (value, distinct) {
+        (
+            Some(
+                Value::Gen(_)
+                | Value::GenIter(_)
+                | Value::TryGen { .. }
+                | Value::Default
+                | Value::DefaultExpr(_)
+                | Value::ByteLen(_)
+                | Value::Range(_)
+                | Value::SystemTime
+                | Value::Each(_)
+                | Value::Ip { .. }
+                | Value::Char(_)
+                | Value::Duration { .. }
+                | Value::HeadTail { .. }
+                | Value::RepeatWith { .. }
+                | Value::Strategy(_),
+            ),
+            true,
+        ) => {
+            // only with `len`
+            return Err(syn::Error::new_spanned(
+                field,
+                format!(
+                    "`distinct` can't be combined with {}",
+                    list(VALUE_ARGS.iter().copied().filter(|it| *it != "len"), "or"),
+                ),
+            ));
+        }
+        (Some(Value::Gen(custom)), false) => {
+            call_gen(&custom, &field.ty.to_token_stream(), gen_name, cx.with_gen)
+        }
+        (Some(Value::GenIter(custom)), false) => {
+            collect_gen(&custom, &field.ty, gen_name, cx.with_gen)
+        }
+        (Some(Value::TryGen { gen, or }), false) => {
+            let ty = &field.ty;
+            let generate = call_gen(
+                &gen,
+                &quote!(::core::result::Result<#ty, _>),
+                gen_name,
+                cx.with_gen,
+            );
+            let fallback = match or {
+                Some(expr) => quote!({
+                    let value: #ty = #expr;
+                    value
+                }),
+                None => quote!(::core::default::Default::default()),
+            };
+            quote! {
+                ::core::iter::Iterator::find_map(
+                    &mut ::core::iter::Iterator::take(::core::iter::repeat_with(|| #generate), #RETRY_ATTEMPTS),
+                    ::core::result::Result::ok,
+                )
+                .unwrap_or_else(|| #fallback)
+            }
+        }
+        (Some(Value::Default), false) => {
+            quote!(::core::default::Default::default())
+        }
+        (Some(Value::DefaultExpr(expr)), false) => {
+            let ty = &field.ty;
+            quote!({
+                let value: #ty = #expr;
+                value
+            })
+        }
+        (Some(Value::SystemTime), false) => quote! {
+            ::std::time::UNIX_EPOCH
+                + ::core::time::Duration::from_secs(
+                    <::core::primitive::u32 as ::quickcheck::Arbitrary>::arbitrary(#gen_name) as ::core::primitive::u64,
+                )
+        },
+        (Some(Value::Range(range)), false) => int_range_value(&field.ty, &range, &label, gen_name),
+        (Some(Value::Ip { v6 }), false) => {
+            let ip = match v6 {
+                false => quote! {
+                    ::std::net::IpAddr::V4(<::std::net::Ipv4Addr as ::quickcheck::Arbitrary>::arbitrary(#gen_name))
+                },
+                true => quote! {
+                    ::std::net::IpAddr::V6(<::std::net::Ipv6Addr as ::quickcheck::Arbitrary>::arbitrary(#gen_name))
+                },
+            };
+            match is_type_named(&field.ty, &["SocketAddr"]) {
+                true => quote! {
+                    ::std::net::SocketAddr::new(
+                        #ip,
+                        <::core::primitive::u16 as ::quickcheck::Arbitrary>::arbitrary(#gen_name),
+                    )
+                },
+                false => ip,
+            }
+        }
+        (Some(Value::Char(ranges)), false) => char_value(&ranges, &label, gen_name),
+        (Some(Value::Each(args)), false) => {
+            let elem = match &field.ty {
+                Type::Array(it) => &it.elem,
+                other => {
+                    return Err(syn::Error::new_spanned(
+                        other,
+                        "`each` is only valid for array members",
+                    ))
+                }
+            };
+            let attrs = vec![parse_quote!(#[arbitrary(#args)])];
+            if get_args(&attrs)?.order.is_some() {
+                return Err(syn::Error::new_spanned(
+                    args,
+                    "`order` is not valid in `each`, which generates elements in turn",
+                ));
+            }
+            // so the element's own arguments are checked as usual
+            let element = field_value(
+                ix,
+                Field {
+                    attrs,
+                    ty: Type::clone(elem),
+                    ..field.clone()
+                },
+                parent,
+                cx,
+            )?
+            .expr;
+            quote!(::core::array::from_fn(|_| #element))
+        }
+        (Some(Value::Duration { secs }), false) => {
+            let secs = match secs {
+                Some(range) => range_value(
+                    &range,
+                    &quote!(::core::primitive::u64),
+                    // as if it were a `u32`
+                    &quote!(::core::primitive::u32::MAX as ::core::primitive::u64),
+                    &format!("`secs` range is empty for `{}`", label),
+                    gen_name,
+                ),
+                None => quote! {
+                    <::core::primitive::u32 as ::quickcheck::Arbitrary>::arbitrary(#gen_name) as ::core::primitive::u64
+                },
+            };
+            quote!(::core::time::Duration::from_secs(#secs))
+        }
+        (Some(Value::Len(range)), distinct) => {
+            collection_value(&field.ty, &range, distinct, &label, gen_name)
+        }
+        (Some(Value::ByteLen(range)), false) => {
+            let len = range_value(
+                &range,
+                &quote!(::core::primitive::usize),
+                &quote!(::core::cmp::max(start, #gen_name.size())),
+                &format!("`byte_len` range is empty for `{}`", label),
+                gen_name,
+            );
+            quote! {{
+                let len = #len;
+                let mut string = ::std::string::String::with_capacity(len);
+                while string.len() < len {
+                    let c = <::core::primitive::char as ::quickcheck::Arbitrary>::arbitrary(#gen_name);
+                    match string.len() + c.len_utf8() <= len {
+                        true => string.push(c),
+                        // an ASCII char always fits
+                        false => string.push(
+                            (c as ::core::primitive::u32 % 0x80) as ::core::primitive::u8 as ::core::primitive::char
+                        ),
+                    }
+                }
+                string
+            }}
+        }
+        (Some(Value::HeadTail { head, tail_len }), false) => {
+            let ty = &field.ty;
+            let item = collection_item(ty);
+            let len = len_value(&tail_len, &label, gen_name);
+            let head = call_gen(&head, &item, gen_name, cx.with_gen);
+            quote! {{
+                let head = #head;
+                let len = #len;
+                ::core::iter::Iterator::collect::<#ty>(::core::iter::Iterator::chain(
+                    ::core::iter::once(head),
+                    ::core::iter::Iterator::take(
+                        ::core::iter::repeat_with(|| ::quickcheck::Arbitrary::arbitrary(&mut *#gen_name)),
+                        len,
+                    ),
+                ))
+            }}
+        }
+        (Some(Value::Strategy(strategy)), false) => {
+            let ty = &field.ty;
+            quote!({
+                let value: #ty = (#strategy).generate(&mut *#gen_name);
+                value
+            })
+        }
+        (Some(Value::RepeatWith { gen, len }), false) => {
+            let ty = &field.ty;
+            let len = len_value(&len, &label, gen_name);
+            let element = call_gen(&gen, &collection_item(ty), gen_name, cx.with_gen);
+            quote! {{
+                let len = #len;
+                ::core::iter::Iterator::collect::<#ty>(
+                    ::core::iter::Iterator::take(::core::iter::repeat_with(|| #element), len),
+                )
+            }}
+        }
+        // modelled as `len(..)`
+        (None, true) => collection_value(&field.ty, &parse_quote!(..), true, &label, gen_name),
+        // the same `Gen`, so the nested struct sees the outer size
+        (None, false) if flatten => quote!(::quickcheck::Arbitrary::arbitrary(#gen_name)),
+        (None, false) if marker_value(&field.ty).is_some() => marker_value(&field.ty).unwrap(),
+        (None, false)
+            if cx
+                .item
+                .is_some_and(|item| is_recursive_option(&field.ty, item)) =>
+        {
+            cx.recursive = true;
+            recursive_option_value(gen_name)
+        }
+        (None, false) if is_callable(&field.ty) => {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                format!(
+                    "field `{}: {}` cannot be generated automatically; provide `#[arbitrary(gen(...))]`",
+                    name,
+                    type_name(&field.ty),
+                ),
+            ));
+        }
+        (None, false) if owned_pointee(&field.ty).is_some() => {
+            let owned = owned_pointee(&field.ty);
+            cx.predicates
+                .push(parse_quote!(#owned: ::quickcheck::Arbitrary));
+            quote! {
+                ::core::convert::Into::into(<#owned as ::quickcheck::Arbitrary>::arbitrary(#gen_name))
+            }
+        }
+        (None, false) if boxed_array(&field.ty).is_some() => {
+            let TypeArray { elem, len, .. } = boxed_array(&field.ty).unwrap();
+            cx.predicates
+                .push(parse_quote!(#elem: ::quickcheck::Arbitrary));
+            quote! {
+                ::std::boxed::Box::new(::core::array::from_fn::<#elem, { #len }, _>(|_| {
+                    <#elem as ::quickcheck::Arbitrary>::arbitrary(&mut *#gen_name)
+                }))
+            }
+        }
+        (None, false) if interior_mutable_inner(&field.ty).is_some() => {
+            let ty = &field.ty;
+            let inner = interior_mutable_inner(ty);
+            cx.predicates
+                .push(parse_quote!(#inner: ::quickcheck::Arbitrary));
+            quote! {
+                <#ty>::new(<#inner as ::quickcheck::Arbitrary>::arbitrary(#gen_name))
+            }
+        }
+        (None, false) if hashed_collection(&field.ty).is_some() => {
+            let (key, value, _) = hashed_collection(&field.ty).unwrap();
+            cx.predicates.push(parse_quote! {
+                #key: ::quickcheck::Arbitrary + ::core::cmp::Eq + ::core::hash::Hash
+            });
+            if let Some(value) = value {
+                cx.predicates
+                    .push(parse_quote!(#value: ::quickcheck::Arbitrary));
+            }
+            quote!(::quickcheck::Arbitrary::arbitrary(#gen_name))
+        }
+        (None, false) if is_type_named(&field.ty, &["Instant"]) => {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                format!(
+                    "field `{}: {}` cannot be generated automatically, because an `Instant` can't be built portably; provide `#[arbitrary(gen(...))]`, e.g. `gen(|_| Instant::now())`",
+                    name,
+                    type_name(&field.ty),
+                ),
+            ));
+        }
+        (None, false) if matches!(field.ty, Type::Array(_)) => {
+            return Err(syn::Error::new_spanned(
+                &field.ty,
+                format!(
+                    "field `{}: {}` cannot be generated automatically, because arrays don't implement `Arbitrary`; provide `#[arbitrary(each(...))]` for the elements, or `#[arbitrary(gen(...))]`",
+                    name,
+                    type_name(&field.ty),
+                ),
+            ));
+        }
+        (None, false) if finite => {
+            let ty = &field.ty;
+            let exhausted = format!(
+                "couldn't generate a finite value for `{}` after {} attempts",
+                label, RETRY_ATTEMPTS
+            );
+            quote! {
+                ::core::iter::Iterator::find(
+                    &mut ::core::iter::Iterator::take(
+                        ::core::iter::repeat_with(|| <#ty as ::quickcheck::Arbitrary>::arbitrary(&mut *#gen_name)),
+                        #RETRY_ATTEMPTS,
+                    ),
+                    |it| it.is_finite(),
+                )
+                .expect(#exhausted)
+            }
+        }
+        (None, false) => quote!(::quickcheck::Arbitrary::arbitrary(#gen_name)),
+    };
+    Ok(FieldValue {
+        attrs: vec![],
+        member: match field.ident {
+            Some(name) => Member::Named(name),
+            None => Member::Unnamed(Index::from(ix)),
+        },
+        colon_token: Some(Colon::default()),
+        expr: Expr::Verbatim(value),
+    })
+}
+
+/// How a member is shrunk.
+#[derive(Clone)]
+enum Shrink {
+    /// With its own [`quickcheck::Arbitrary::shrink`].
+    Free,
+    /// Held constant.
+    Pinned,
+    /// With a `gen_fn` strategy's `shrink`.
+    With(Expr),
+    /// With its own [`quickcheck::Arbitrary::shrink`], despite being generated by a strategy.
+    Type,
+}
+
+/// Each member, and how it's shrunk.
+///
+/// Members with a custom generation strategy are held constant, since their
+/// [`quickcheck::Arbitrary::shrink`] might not respect it.
+/// `gen_fn` strategies shrink themselves.
+fn shrink_members(fields: &Fields, respect_serde_skip: bool) -> syn::Result<Vec<(Member, Shrink)>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(ix, field)| {
+            let Arg {
+                value,
+                distinct,
+                finite,
+                flatten,
+                type_shrink,
+                ..
+            } = get_args(&field.attrs)?;
+            let member = match &field.ident {
+                Some(name) => Member::Named(name.clone()),
+                None => Member::Unnamed(Index::from(ix)),
+            };
+            // no shrink for these
+            let unsized_pointee = owned_pointee(&field.ty).is_some();
+            let boxed_array = boxed_array(&field.ty).is_some();
+            let interior_mutable = interior_mutable_inner(&field.ty).is_some();
+            let marker = marker_value(&field.ty).is_some();
+            let serde_skipped = respect_serde_skip
+                && !distinct
+                && !finite
+                && !flatten
+                && is_serde_skipped(&field.attrs);
+            let shrink = match value {
+                Some(Value::Strategy(strategy)) => Shrink::With(strategy),
+                Some(_) if type_shrink => Shrink::Type,
+                None if !distinct
+                    && !finite
+                    && !unsized_pointee
+                    && !boxed_array
+                    && !interior_mutable
+                    && !marker
+                    && !serde_skipped =>
+                {
+                    Shrink::Free
+                }
+                _ => Shrink::Pinned,
+            };
+            Ok((member, shrink))
+        })
+        .collect()
+}
+
+/// A `match self` arm that shrinks the free members of `path`, holding the rest constant.
+///
+/// They're shrunk together as a tuple, or with `in_place`, one at a time like `gen_fn` members.
+fn shrink_arm(path: &Path, members: &[(Member, Shrink)], in_place: bool) -> TokenStream {
+    // named by index rather than by field, so they never trip e.g. `non_snake_case`
+    let bindings = (0..members.len())
+        .map(|ix| Ident::new(&format!("f{}", ix), Span::call_site()))
+        .collect::<Vec<_>>();
+    // only shrunk members are bound, so nothing else needs to be `Clone` by itself
+    let shrunk = members
+        .iter()
+        .zip(&bindings)
+        .filter(|((_, shrink), _)| !matches!(shrink, Shrink::Pinned))
+        .map(|((member, _), binding)| (member, binding))
+        .collect::<Vec<_>>();
+    let bound = shrunk.iter().map(|(member, _)| member);
+    let bound_bindings = shrunk.iter().map(|(_, binding)| binding);
+    let free = members
+        .iter()
+        .zip(&bindings)
+        .filter(|((_, shrink), _)| !in_place && matches!(shrink, Shrink::Free | Shrink::Type))
+        .map(|((member, _), binding)| (member, binding))
+        .collect::<Vec<_>>();
+    let body = match free.is_empty() {
+        true => quote!(::quickcheck::empty_shrinker()),
+        false => {
+            let tuple = nested_tuple(&free.iter().map(|(_, it)| it).collect::<Vec<_>>());
+            let free_bindings = free.iter().map(|(_, it)| it);
+            let rebuilt = overwrite(path, &free);
+            quote! {
+                let this = ::core::clone::Clone::clone(self);
+                ::std::boxed::Box::new(::core::iter::Iterator::map(
+                    ::quickcheck::Arbitrary::shrink(&{
+                        #(let #free_bindings = ::core::clone::Clone::clone(#free_bindings);)*
+                        #tuple
+                    }),
+                    move |#tuple| #rebuilt,
+                ))
+            }
+        }
+    };
+    // then each `gen_fn` member on its own, holding the rest constant
+    let strategies = members
+        .iter()
+        .zip(&bindings)
+        .filter_map(|((member, shrink), binding)| match shrink {
+            Shrink::With(strategy) => Some((quote!((#strategy).shrink(#binding)), member, binding)),
+            Shrink::Free | Shrink::Type if in_place => Some((
+                quote!(::quickcheck::Arbitrary::shrink(#binding)),
+                member,
+                binding,
+            )),
+            _ => None,
+        })
+        .map(|(shrunk, member, binding)| {
+            let rebuilt = overwrite(path, &[(member, binding)]);
+            quote! {{
+                let this = ::core::clone::Clone::clone(self);
+                ::std::boxed::Box::new(::core::iter::Iterator::map(
+                    ::core::iter::IntoIterator::into_iter(#shrunk),
+                    move |#binding| #rebuilt,
+                )) as ::std::boxed::Box<dyn ::core::iter::Iterator<Item = Self>>
+            }}
+        })
+        .collect::<Vec<_>>();
+    let body = match (strategies.is_empty(), free.is_empty()) {
+        (true, _) => body,
+        (false, true) => quote! {
+            let shrinks = [#(#strategies,)*];
+            ::std::boxed::Box::new(::core::iter::Iterator::flatten(
+                ::core::iter::IntoIterator::into_iter(shrinks),
+            ))
+        },
+        (false, false) => quote! {
+            let shrinks = [
+                { #body } as ::std::boxed::Box<dyn ::core::iter::Iterator<Item = Self>>,
+                #(#strategies,)*
+            ];
+            ::std::boxed::Box::new(::core::iter::Iterator::flatten(
+                ::core::iter::IntoIterator::into_iter(shrinks),
+            ))
+        },
+    };
+    quote! {
+        #path { #(#bound: #bound_bindings,)* .. } => { #body }
+    }
+}
+
+/// A copy of `this`, which is a `path`, with `members` set from their bindings.
+pub(crate) fn overwrite(path: &Path, members: &[(&Member, &Ident)]) -> TokenStream {
+    let names = members.iter().map(|(member, _)| member);
+    let bindings = members.iter().map(|(_, binding)| binding);
+    // `Enum::Variant`
+    let body = match path.segments.len() > 1 {
+        true => {
+            let slots =
+                (0..members.len()).map(|ix| Ident::new(&format!("s{}", ix), Span::call_site()));
+            let assigned = slots.clone();
+            quote! {
+                #[allow(unreachable_patterns)]
+                match &mut it {
+                    #path { #(#names: #slots,)* .. } => {
+                        #(*#assigned = #bindings;)*
+                    }
+                    _ => ::core::unreachable!(),
+                }
+            }
+        }
+        // no references, in case it's packed
+        false => quote!(#(it.#names = #bindings;)*),
+    };
+    quote! {{
+        let mut it = ::core::clone::Clone::clone(&this);
+        #body
+        it
+    }}
+}
+
+/// `quickcheck` only implements [`quickcheck::Arbitrary`] for tuples of up to 8,
+/// so nest them as required.
+///
+/// This is valid as both an expression and a pattern.
+fn nested_tuple<T: quote::ToTokens>(items: &[T]) -> TokenStream {
+    match items.len() <= 8 {
+        true => quote!((#(#items,)*)),
+        false => {
+            // at most 8 chunks
+            let chunks = items.chunks((items.len() + 7) / 8).map(nested_tuple);
+            quote!((#(#chunks,)*))
+        }
+    }
+}
+
+/// The element type of a collection `ty`, for generators.
+fn collection_item(ty: &Type) -> TokenStream {
+    match ty {
+        Type::Infer(_) => quote!(_),
+        // point errors at the type
+        ty => quote_spanned!(ty.span()=> <#ty as ::core::iter::IntoIterator>::Item),
+    }
+}
+
+/// Call a user's generator, which must return exactly `ty`.
+///
+/// With `with_gen`, the generator takes that type instead, made from the `Gen` for each call.
+fn call_gen(
+    custom: &TokenStream,
+    ty: &TokenStream,
+    gen_name: &TokenStream,
+    with_gen: Option<&Type>,
+) -> TokenStream {
+    let (param, wrap, arg) = generator_arg(gen_name, with_gen);
+    // A typed `let` is a coercion site, so closures and (generic) fn items
+    // are inferred against the field type, not just cast to it.
+    // (not `gen`, which is reserved in edition 2024)
+    match takes_built(custom) {
+        true => quote! {{
+            let generate: fn(&mut #param, &__Built) -> #ty = #custom;
+            #wrap
+            generate(#arg, &built)
+        }},
+        false => quote! {{
+            let generate: fn(&mut #param) -> #ty = #custom;
+            #wrap
+            generate(#arg)
+        }},
+    }
+}
+
+/// Collect the iterator from a user's generator into `ty`.
+fn collect_gen(
+    custom: &TokenStream,
+    ty: &Type,
+    gen_name: &TokenStream,
+    with_gen: Option<&Type>,
+) -> TokenStream {
+    let (_, wrap, arg) = generator_arg(gen_name, with_gen);
+    // point errors at the type
+    let collect = quote_spanned!(ty.span()=> collect(#arg, #custom));
+    // The iterator may borrow the argument, so the closure takes it for one named lifetime,
+    // which a fn pointer type can't express.
+    quote! {{
+        fn collect<'a, G: ?::core::marker::Sized, I, C>(
+            g: &'a mut G,
+            generate: impl ::core::ops::FnOnce(&'a mut G) -> I,
+        ) -> C
+        where
+            I: ::core::iter::IntoIterator,
+            C: ::core::iter::FromIterator<I::Item>,
+        {
+            ::core::iter::FromIterator::from_iter(generate(g))
+        }
+        #wrap
+        let collected: #ty = #collect;
+        collected
+    }}
+}
+
+/// The type a generator takes, any setup, and the argument to pass it.
+///
+/// With `with_gen`, that's the wrapper, made from the `Gen` for each call.
+fn generator_arg(
+    gen_name: &TokenStream,
+    with_gen: Option<&Type>,
+) -> (TokenStream, TokenStream, TokenStream) {
+    match with_gen {
+        Some(wrapper) => (
+            quote!(#wrapper),
+            quote!(let mut wrapped: #wrapper = ::core::convert::From::from(&mut *#gen_name);),
+            quote!(&mut wrapped),
+        ),
+        None => (
+            quote!(::quickcheck::Gen),
+            TokenStream::new(),
+            quote!(&mut *#gen_name),
+        ),
+    }
+}
+
+/// Whether `custom` is a closure like `|g, built| ...`.
+fn takes_built(custom: &TokenStream) -> bool {
+    matches!(syn::parse2(custom.clone()), Ok(Expr::Closure(ExprClosure { inputs, .. })) if inputs.len() == 2)
+}
+
+/// How many times as likely each other variant is as a `rare` one.
+const COMMON_WEIGHT: usize = 10;
+
+/// How many times to try and generate a value before giving up.
+const RETRY_ATTEMPTS: usize = 100;
+
+/// Collect a generated number of elements into `ty`, trying [`RETRY_ATTEMPTS`]
+/// times for each new element if `distinct`.
+///
+/// `label` identifies the field in panic messages.
+// Don't try and recognise the collection, so that type aliases etc. work
+fn collection_value(
+    ty: &Type,
+    range: &Expr,
+    distinct: bool,
+    label: &str,
+    gen_name: &TokenStream,
+) -> TokenStream {
+    let len = len_value(range, label, gen_name);
+    // `String` collects several item types, so `len` counts `char`s
+    let item = match is_type_named(ty, &["String"]) {
+        true => quote!(::core::primitive::char),
+        false => quote!(_),
+    };
+    let exhausted = format!(
+        "couldn't generate a distinct element for `{}` after {} attempts",
+        label, RETRY_ATTEMPTS
+    );
+    match distinct {
+        true => quote! {{
+            let len = #len;
+            let mut items = ::std::vec::Vec::with_capacity(len);
+            while items.len() < len {
+                let item = ::core::iter::Iterator::find(
+                    &mut ::core::iter::Iterator::take(
+                        ::core::iter::repeat_with(|| <#item as ::quickcheck::Arbitrary>::arbitrary(&mut *#gen_name)),
+                        #RETRY_ATTEMPTS,
+                    ),
+                    |it| !items.contains(it),
+                )
+                .expect(#exhausted);
+                items.push(item);
+            }
+            ::core::iter::Iterator::collect::<#ty>(::core::iter::IntoIterator::into_iter(items))
+        }},
+        false => quote! {{
+            let len = #len;
+            ::core::iter::Iterator::collect::<#ty>(::core::iter::Iterator::take(
+                ::core::iter::repeat_with(|| <#item as ::quickcheck::Arbitrary>::arbitrary(&mut *#gen_name)),
+                len,
+            ))
+        }},
+    }
+}
+
+/// Pick a length from a [`RangeBounds<usize>`](core::ops::RangeBounds) expression.
+///
+/// Open-ended ranges are capped at [`quickcheck::Gen::size`].
+fn len_value(range: &Expr, label: &str, gen_name: &TokenStream) -> TokenStream {
+    range_value(
+        range,
+        &quote!(::core::primitive::usize),
+        // keep generation finite, but respect the lower bound
+        &quote!(::core::cmp::max(start, #gen_name.size())),
+        &format!("`len` range is empty for `{}`", label),
+        gen_name,
+    )
+}
+
+/// Pick an `int` from a [`RangeBounds`](core::ops::RangeBounds) expression.
+///
+/// `unbounded_end` may refer to the `start`, and `empty` is the panic message.
+fn range_value(
+    range: &Expr,
+    int: &TokenStream,
+    unbounded_end: &TokenStream,
+    empty: &str,
+    gen_name: &TokenStream,
+) -> TokenStream {
+    quote! {{
+        let range = #range;
+        let start = match ::core::ops::RangeBounds::<#int>::start_bound(&range) {
+            ::core::ops::Bound::Included(it) => *it,
+            ::core::ops::Bound::Excluded(it) => *it + 1,
+            ::core::ops::Bound::Unbounded => 0,
+        };
+        let end = match ::core::ops::RangeBounds::<#int>::end_bound(&range) {
+            ::core::ops::Bound::Included(it) => *it,
+            ::core::ops::Bound::Excluded(it) => it.checked_sub(1).expect(#empty),
+            ::core::ops::Bound::Unbounded => #unbounded_end,
+        };
+        ::core::assert!(start <= end, "{}", #empty);
+        match (end - start).checked_add(1) {
+            ::core::option::Option::Some(span) => {
+                start + <#int as ::quickcheck::Arbitrary>::arbitrary(#gen_name) % span
+            }
+            ::core::option::Option::None => <#int as ::quickcheck::Arbitrary>::arbitrary(#gen_name),
+        }
+    }}
+}
+
+/// Integer types that fit in an `i128` with room to spare, for `range`.
+const INTS: &[&str] = &[
+    "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize",
+];
+
+/// Pick an integer of type `ty` from a [`RangeBounds`](core::ops::RangeBounds) expression.
+///
+/// The arithmetic is done in `i128`, so signed ranges don't overflow.
+fn int_range_value(ty: &Type, range: &Expr, label: &str, gen_name: &TokenStream) -> TokenStream {
+    let empty = format!("`range` is empty for `{}`", label);
+    quote! {{
+        let range = #range;
+        let start = match ::core::ops::RangeBounds::<#ty>::start_bound(&range) {
+            ::core::ops::Bound::Included(it) => *it as ::core::primitive::i128,
+            ::core::ops::Bound::Excluded(it) => *it as ::core::primitive::i128 + 1,
+            ::core::ops::Bound::Unbounded => <#ty>::MIN as ::core::primitive::i128,
+        };
+        let end = match ::core::ops::RangeBounds::<#ty>::end_bound(&range) {
+            ::core::ops::Bound::Included(it) => *it as ::core::primitive::i128,
+            ::core::ops::Bound::Excluded(it) => *it as ::core::primitive::i128 - 1,
+            ::core::ops::Bound::Unbounded => <#ty>::MAX as ::core::primitive::i128,
+        };
+        ::core::assert!(start <= end, "{}", #empty);
+        // at most 2^64
+        let span = (end - start + 1) as ::core::primitive::u128;
+        let offset = <::core::primitive::u64 as ::quickcheck::Arbitrary>::arbitrary(#gen_name)
+            as ::core::primitive::u128
+            % span;
+        (start + offset as ::core::primitive::i128) as #ty
+    }}
+}
+
+/// The `RangeInclusive<char>`s for `char(...)`.
+fn char_ranges(name_span: Span, args: CharArgs) -> syn::Result<Vec<Expr>> {
+    match args {
+        CharArgs {
+            ascii: true,
+            alphanumeric: false,
+            ranges: None,
+        } => Ok(vec![parse_quote!('\0'..='\x7f')]),
+        CharArgs {
+            ascii: false,
+            alphanumeric: true,
+            ranges: None,
+        } => Ok(vec![
+            parse_quote!('0'..='9'),
+            parse_quote!('A'..='Z'),
+            parse_quote!('a'..='z'),
+        ]),
+        CharArgs {
+            ascii: false,
+            alphanumeric: false,
+            ranges: Some(NameValue { name_span, value }),
+        } => match syn::parse2::<ExprArray>(value.clone()) {
+            Ok(ExprArray { elems, .. }) if !elems.is_empty() => Ok(elems.into_iter().collect()),
+            _ => Err(syn::Error::new(
+                name_span,
+                "`ranges` must be a non-empty array of `char` ranges: `ranges = ['a'..='z']`",
+            )),
+        },
+        _ => Err(syn::Error::new(
+            name_span,
+            "`char` requires exactly one of `ascii`, `alphanumeric` or `ranges = [...]`",
+        )),
+    }
+}
+
+/// Pick a `char` uniformly from `ranges`, skipping the surrogates.
+fn char_value(ranges: &[Expr], label: &str, gen_name: &TokenStream) -> TokenStream {
+    let empty = format!("`char` ranges are empty for `{}`", label);
+    quote! {{
+        let ranges: &[::core::ops::RangeInclusive<::core::primitive::char>] = &[#(#ranges),*];
+        // neither end is a surrogate, so a range holds all of them or none
+        let len = |range: &::core::ops::RangeInclusive<::core::primitive::char>| {
+            let (start, end) = (
+                *range.start() as ::core::primitive::u32,
+                *range.end() as ::core::primitive::u32,
+            );
+            match (start <= end, start < 0xD800 && end > 0xDFFF) {
+                (false, _) => 0,
+                (true, false) => end - start + 1,
+                (true, true) => end - start + 1 - 0x800,
+            }
+        };
+        let total = ::core::iter::Iterator::sum::<::core::primitive::u64>(::core::iter::Iterator::map(
+            ranges.iter(),
+            |it| len(it) as ::core::primitive::u64,
+        ));
+        ::core::assert!(total > 0, "{}", #empty);
+        let mut offset = (<::core::primitive::u64 as ::quickcheck::Arbitrary>::arbitrary(#gen_name) % total)
+            as ::core::primitive::u32;
+        let range = ::core::iter::Iterator::find(&mut ranges.iter(), |it| match offset < len(it) {
+            true => true,
+            false => {
+                offset -= len(it);
+                false
+            }
+        })
+        .expect("offset is within the total");
+        let mut scalar = *range.start() as ::core::primitive::u32 + offset;
+        if (*range.start() as ::core::primitive::u32) < 0xD800 && scalar >= 0xD800 {
+            scalar += 0x800;
+        }
+        ::core::char::from_u32(scalar).expect("surrogates are skipped")
+    }}
+}
+
+/// Whether two variants have the same kind of fields, with the same names and types.
+fn same_shape(left: &Fields, right: &Fields) -> bool {
+    let shape = |fields: &Fields| {
+        fields
+            .iter()
+            .map(|it| (it.ident.clone(), type_name(&it.ty)))
+            .collect::<Vec<_>>()
+    };
+    matches!(
+        (left, right),
+        (Fields::Named(_), Fields::Named(_)) | (Fields::Unnamed(_), Fields::Unnamed(_))
+    ) && shape(left) == shape(right)
+}
+
+/// Function pointers and (boxed) closures, which can never be [`quickcheck::Arbitrary`].
+fn is_callable(ty: &Type) -> bool {
+    match ty {
+        Type::BareFn(_) => true,
+        Type::Group(TypeGroup { elem, .. })
+        | Type::Paren(TypeParen { elem, .. })
+        | Type::Reference(TypeReference { elem, .. }) => is_callable(elem),
+        Type::TraitObject(TypeTraitObject { bounds, .. }) => bounds.iter().any(|it| {
+            matches!(it, TypeParamBound::Trait(TraitBound { path, .. })
+                if path.segments.last().is_some_and(|it| matches!(it.ident.to_string().as_str(), "Fn" | "FnMut" | "FnOnce")))
+        }),
+        // Box<dyn Fn()> etc.
+        Type::Path(TypePath { qself: None, path }) => match path.segments.last() {
+            Some(PathSegment {
+                ident,
+                arguments: PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }),
+            }) if ident == "Box" || ident == "Rc" || ident == "Arc" => {
+                matches!(args.first(), Some(GenericArgument::Type(inner)) if is_callable(inner))
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// The `T` in `Cell<T>`, `RefCell<T>`, `Mutex<T>` or `RwLock<T>`,
+/// which don't implement [`quickcheck::Arbitrary`], but are built infallibly with `new`.
+fn interior_mutable_inner(ty: &Type) -> Option<Type> {
+    match ty {
+        Type::Group(TypeGroup { elem, .. }) | Type::Paren(TypeParen { elem, .. }) => {
+            interior_mutable_inner(elem)
+        }
+        Type::Path(TypePath { qself: None, path }) => match path.segments.last() {
+            Some(PathSegment {
+                ident,
+                arguments:
+                    PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }),
+            }) if args.len() == 1
+                && ["Cell", "RefCell", "Mutex", "RwLock"]
+                    .iter()
+                    .any(|name| ident == name) =>
+            {
+                match args.first() {
+                    Some(GenericArgument::Type(inner)) => Some(inner.clone()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The key, value (for a map) and hasher of a `HashMap<K, V, S>` or `HashSet<T, S>`,
+/// whose impls need bounds on all of them, not just on the collection.
+fn hashed_collection(ty: &Type) -> Option<(Type, Option<Type>, Type)> {
+    match ty {
+        Type::Group(TypeGroup { elem, .. }) | Type::Paren(TypeParen { elem, .. }) => {
+            hashed_collection(elem)
+        }
+        Type::Path(TypePath { qself: None, path }) => match path.segments.last() {
+            Some(PathSegment {
+                ident,
+                arguments:
+                    PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }),
+            }) => {
+                let args = args
+                    .iter()
+                    .map(|arg| match arg {
+                        GenericArgument::Type(ty) => Some(ty.clone()),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()?;
+                match (ident.to_string().as_str(), &args[..]) {
+                    ("HashMap", [key, value, hasher]) => {
+                        Some((key.clone(), Some(value.clone()), hasher.clone()))
+                    }
+                    ("HashSet", [item, hasher]) => Some((item.clone(), None, hasher.clone())),
+                    _ => None,
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `Box<str>` etc. don't implement [`quickcheck::Arbitrary`], but `String` does.
+///
+/// For `Box`, `Rc` or `Arc` of `str` or `[T]`, returns `String` or `Vec<T>`.
+fn owned_pointee(ty: &Type) -> Option<Type> {
+    match ty {
+        Type::Group(TypeGroup { elem, .. }) | Type::Paren(TypeParen { elem, .. }) => {
+            owned_pointee(elem)
+        }
+        Type::Path(TypePath { qself: None, path }) => match path.segments.last() {
+            Some(PathSegment {
+                ident,
+                arguments:
+                    PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }),
+            }) if args.len() == 1 && (ident == "Box" || ident == "Rc" || ident == "Arc") => {
+                match args.first() {
+                    Some(GenericArgument::Type(inner)) if is_type_named(inner, &["str"]) => {
+                        Some(parse_quote!(::std::string::String))
+                    }
+                    Some(GenericArgument::Type(Type::Slice(TypeSlice { elem, .. }))) => {
+                        Some(parse_quote!(::std::vec::Vec<#elem>))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `[T; N]` from `Box<[T; N]>`, which is built element by element, since arrays don't implement [`quickcheck::Arbitrary`].
+fn boxed_array(ty: &Type) -> Option<&TypeArray> {
+    match wrapped(ty, "Box") {
+        Some(Type::Array(array)) => Some(array),
+        _ => None,
+    }
+}
+
+/// The only value of a zero-sized marker type, built without the `Gen`.
+fn marker_value(ty: &Type) -> Option<TokenStream> {
+    match ty {
+        Type::Group(TypeGroup { elem, .. }) | Type::Paren(TypeParen { elem, .. }) => {
+            marker_value(elem)
+        }
+        Type::Tuple(TypeTuple { elems, .. }) if elems.is_empty() => Some(quote!(())),
+        ty if is_type_named(ty, &["PhantomPinned"]) => Some(quote!(::core::marker::PhantomPinned)),
+        _ => None,
+    }
+}
+
+/// `Option<Box<Self>>`, or the item by its bare name, which is how recursive types usually end.
+///
+/// A path like `other::Node` is some other type, even if it ends in the item's name.
+fn is_recursive_option(ty: &Type, item: &Ident) -> bool {
+    wrapped(ty, "Option")
+        .and_then(|it| wrapped(it, "Box"))
+        .is_some_and(|it| match it {
+            Type::Path(TypePath { qself: None, path }) if path.leading_colon.is_none() => {
+                match path.segments.iter().collect::<Vec<_>>().as_slice() {
+                    [only] => only.ident == "Self" || only.ident == *item,
+                    _ => false,
+                }
+            }
+            _ => false,
+        })
+}
+
+/// `T` from `name<T>`.
+fn wrapped<'a>(ty: &'a Type, name: &str) -> Option<&'a Type> {
+    match ty {
+        Type::Group(TypeGroup { elem, .. }) | Type::Paren(TypeParen { elem, .. }) => {
+            wrapped(elem, name)
+        }
+        Type::Path(TypePath { qself: None, path }) => match path.segments.last() {
+            Some(PathSegment {
+                ident,
+                arguments:
+                    PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }),
+            }) if args.len() == 1 && ident == name => match args.first() {
+                Some(GenericArgument::Type(inner)) => Some(inner),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Generate an `Option<Box<Self>>` that's `None` more often the deeper it's nested.
+///
+/// Each level sees half the size of the one above, and is `Some` with probability
+/// `size / (size + 1)`, so nesting stops by `log2(g.size())` levels.
+/// The depth is declared once for the item, by [`expand_impls`].
+fn recursive_option_value(gen_name: &TokenStream) -> TokenStream {
+    quote!({
+        let depth = __RECURSION_DEPTH.with(::core::cell::Cell::get);
+        let size = #gen_name.size().checked_shr(depth).unwrap_or(0);
+        let draw = <::core::primitive::u32 as ::quickcheck::Arbitrary>::arbitrary(#gen_name)
+            as ::core::primitive::usize;
+        match size > 0 && draw % (size + 1) != 0 {
+            true => {
+                // even if generating the inner value panics
+                struct Restore(::core::primitive::u32);
+                impl ::core::ops::Drop for Restore {
+                    fn drop(&mut self) {
+                        __RECURSION_DEPTH.with(|it| it.set(self.0));
+                    }
+                }
+                let _restore = Restore(depth);
+                __RECURSION_DEPTH.with(|it| it.set(depth + 1));
+                ::core::option::Option::Some(::quickcheck::Arbitrary::arbitrary(&mut *#gen_name))
+            }
+            false => ::core::option::Option::None,
+        }
+    })
+}
+
+fn is_float(ty: &Type) -> bool {
+    is_type_named(ty, &["f32", "f64"])
+}
+
+/// Whether `ty` is a non-generic path ending in one of `names`.
+fn is_type_named(ty: &Type, names: &[&str]) -> bool {
+    match ty {
+        Type::Group(TypeGroup { elem, .. }) | Type::Paren(TypeParen { elem, .. }) => {
+            is_type_named(elem, names)
+        }
+        Type::Path(TypePath { qself: None, path }) => path
+            .segments
+            .last()
+            .is_some_and(|it| it.arguments.is_none() && names.iter().any(|name| it.ident == name)),
+        _ => false,
+    }
+}
+
+/// Roughly how the user would write `ty`, for error messages.
+fn type_name(ty: &Type) -> String {
+    ty.to_token_stream()
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(" < ", "<")
+        .replace(" >", ">")
+        .replace("& ", "&")
+}
+
+fn is_deprecated(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|it| it.path().is_ident("deprecated"))
+}
+
+/// `#[repr(packed)]` or `#[repr(packed(N))]`.
+fn is_packed(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|it| it.path().is_ident("repr"))
+        .any(|it| {
+            let mut packed = false;
+            // other reprs are checked by the compiler
+            let _ = it.parse_nested_meta(|meta| {
+                packed |= meta.path.is_ident("packed");
+                if meta.input.peek(syn::token::Paren) {
+                    let _: TokenTree = meta.input.parse()?;
+                }
+                Ok(())
+            });
+            packed
+        })
+}
+
+/// Whether `attrs` contain `#[serde(skip)]`, ignoring anything we don't understand.
+fn is_serde_skipped(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|it| it.path().is_ident("serde"))
+        .any(|it| {
+            let mut skip = false;
+            let _ = it.parse_nested_meta(|meta| {
+                skip |= meta.path.is_ident("skip");
+                if meta.input.peek(Token![=]) {
+                    let _: Expr = meta.value()?.parse()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    let _: TokenTree = meta.input.parse()?;
+                }
+                Ok(())
+            });
+            skip
+        })
+}
+
+/// Each member's `order`, if any.
+fn field_orders(fields: &Fields) -> syn::Result<Vec<Option<u32>>> {
+    fields
+        .iter()
+        .map(|field| get_args(&field.attrs).map(|it| it.order))
+        .collect()
+}
+
+/// Build `path`, generating members with an `order` first, lowest first,
+/// then the rest in declaration order.
+fn ordered_struct(
+    path: Path,
+    fields: &Fields,
+    mut field_values: Punctuated<FieldValue, Comma>,
+    orders: &[Option<u32>],
+) -> TokenStream {
+    if orders.iter().all(Option::is_none) {
+        return expr_struct(path, fields, field_values).into_token_stream();
+    }
+    let mut generation = (0..orders.len()).collect::<Vec<_>>();
+    // stable, so ties keep declaration order too
+    generation.sort_by_key(|ix| (orders[*ix].is_none(), orders[*ix]));
+    let mut exprs = field_values
+        .iter_mut()
+        .enumerate()
+        .map(|(ix, it)| {
+            let binding = Ident::new(&format!("f{}", ix), Span::call_site());
+            let expr = std::mem::replace(&mut it.expr, parse_quote!(#binding));
+            Some((binding, expr))
+        })
+        .collect::<Vec<_>>();
+    let lets = generation.into_iter().map(|ix| {
+        let (binding, expr) = exprs[ix].take().expect("each member is generated once");
+        quote!(let #binding = #expr;)
+    });
+    let ctor = expr_struct(path, fields, field_values);
+    quote!({
+        #(#lets)*
+        #ctor
+    })
+}
+
+/// Build `path` with the same delimiters as its declaration,
+/// so e.g. unit variants aren't written `Self::A {}`.
+fn expr_struct(path: Path, fields: &Fields, field_values: Punctuated<FieldValue, Comma>) -> Expr {
+    match fields {
+        Fields::Named(_) => Expr::Struct(ExprStruct {
+            attrs: vec![],
+            qself: None,
+            path,
+            brace_token: Brace::default(),
+            fields: field_values,
+            dot2_token: None,
+            rest: None,
+        }),
+        // `field_values` are in declaration order
+        Fields::Unnamed(_) => Expr::Call(ExprCall {
+            attrs: vec![],
+            func: Box::new(expr_path(path)),
+            paren_token: Paren::default(),
+            args: field_values.into_iter().map(|it| it.expr).collect(),
+        }),
+        Fields::Unit => expr_path(path),
+    }
+}
+
+fn expr_path(path: Path) -> Expr {
+    Expr::Path(ExprPath {
+        attrs: vec![],
+        qself: None,
+        path,
+    })
+}
+
+fn path_of_idents(idents: impl IntoIterator<Item = Ident>) -> Path {
+    Path {
+        leading_colon: None,
+        segments: Punctuated::from_iter(idents.into_iter().map(|ident| PathSegment {
+            ident,
+            arguments: PathArguments::None,
+        })),
+    }
+}
+
+/// How to generate a member.
+#[derive(Clone)]
+enum Value {
+    Gen(TokenStream),
+    /// A generator returning an iterator, collected into the member.
+    GenIter(TokenStream),
+    /// A generator returning a `Result`, retried before falling back to `or`, or the default.
+    TryGen {
+        gen: TokenStream,
+        or: Option<Expr>,
+    },
+    Default,
+    /// `default = EXPR`, which needs nothing from the member's type.
+    DefaultExpr(Expr),
+    Len(Expr),
+    /// A `String` of exactly this many bytes.
+    ByteLen(Expr),
+    /// An integer within a range.
+    Range(Expr),
+    /// Some time after the [`UNIX_EPOCH`](std::time::UNIX_EPOCH).
+    SystemTime,
+    /// Each element of an array, as if the arguments were on a member of the element type.
+    Each(TokenStream),
+    /// An `IpAddr` or `SocketAddr` of one version.
+    Ip {
+        v6: bool,
+    },
+    /// A `char` from one of these `RangeInclusive<char>`s.
+    Char(Vec<Expr>),
+    /// Whole seconds, within `secs` if given.
+    Duration {
+        secs: Option<Expr>,
+    },
+    /// A collection starting with a custom element.
+    HeadTail {
+        head: TokenStream,
+        tail_len: Expr,
+    },
+    /// A collection of custom elements.
+    RepeatWith {
+        gen: TokenStream,
+        len: Expr,
+    },
+    /// `gen_fn = STRATEGY`, which also shrinks the member.
+    Strategy(Expr),
+}
+
+/// A parsed `#[arbitrary(...)]` attribute.
+///
+/// Whether each argument is valid depends on where the attribute is.
+#[derive(Clone, Default)]
+struct Arg {
+    /// Each argument's name, as written.
+    names: Vec<Ident>,
+    skip: bool,
+    value: Option<Value>,
+    r#where: Option<Punctuated<WherePredicate, Comma>>,
+    include_deprecated: bool,
+    distinct: bool,
+    finite: bool,
+    finite_floats: bool,
+    /// Predicates on the whole generated value, checked in debug builds.
+    validate: Vec<TokenStream>,
+    seed_field: Option<Ident>,
+    flatten: bool,
+    no_shrink_target: bool,
+    respect_serde_skip: bool,
+    portable: bool,
+    round_robin: bool,
+    unbiased: bool,
+    /// Build only the chosen variant, rather than cloning it out of all of them.
+    no_clone: bool,
+    /// Reject generated variants with the same fields.
+    distinct_variants: bool,
+    /// Shrink a member with a strategy as its type would, rather than holding it.
+    type_shrink: bool,
+    /// Count how often each variant is generated.
+    instrument: bool,
+    /// Generate a variant less often than the others.
+    rare: bool,
+    /// Shrink each member on its own, from a copy of the rest.
+    shrink_in_place: bool,
+    /// What generators take instead of the `Gen`.
+    with_gen: Option<Type>,
+    /// Where a member comes in the generation order.
+    order: Option<u32>,
+    /// The size of a fresh `Gen` for the whole item.
+    size: Option<Expr>,
+    /// A `fn() -> bool` that excludes a variant when it returns `true`.
+    exclude_if: Option<Path>,
+    /// Builds the struct from its members, in declaration order.
+    constructor: Option<Path>,
+    /// Implement `proptest::arbitrary::Arbitrary` too.
+    proptest: bool,
+    /// Reject generic members with no bounds, before trait resolution does.
+    error_if_generic: bool,
+}
+
+#[derive(StructMeta, Debug, Default)]
+struct AttrArgs {
+    gen: Option<NameArgs<TokenStream>>,
+    gen_iter: Option<NameArgs<TokenStream>>,
+    skip: bool,
+    default: bool,
+    len: Option<NameArgs<TokenStream>>,
+    byte_len: Option<NameArgs<TokenStream>>,
+    range: Option<NameArgs<TokenStream>>,
+    r#where: Option<NameArgs<TokenStream>>,
+    bound_for: Option<NameArgs<TokenStream>>,
+    include_deprecated: bool,
+    distinct: bool,
+    finite: bool,
+    finite_floats: bool,
+    system_time: bool,
+    duration: Option<NameArgs<Option<DurationArgs>>>,
+    ipv4: bool,
+    ipv6: bool,
+    char: Option<NameArgs<CharArgs>>,
+    try_gen: Option<NameArgs<TryGenArgs>>,
+    validate: Option<NameArgs<TokenStream>>,
+    head: Option<NameArgs<HeadArgs>>,
+    tail_len: Option<NameArgs<TokenStream>>,
+    repeat_with: Option<NameArgs<RepeatWithArgs>>,
+    each: Option<NameArgs<TokenStream>>,
+    seed_field: Option<NameValue<Ident>>,
+    exclude_if: Option<NameValue<TokenStream>>,
+    constructor: Option<NameValue<TokenStream>>,
+    flatten: bool,
+    no_shrink_target: bool,
+    respect_serde_skip: bool,
+    portable: bool,
+    round_robin: bool,
+    unbiased: bool,
+    no_clone: bool,
+    distinct_variants: bool,
+    type_shrink: bool,
+    instrument: bool,
+    rare: bool,
+    shrink_in_place: bool,
+    with_gen: Option<NameArgs<TokenStream>>,
+    proptest: bool,
+    error_if_generic: bool,
+}
+
+/// The inside of `head(...)`.
+#[derive(StructMeta, Debug)]
+struct HeadArgs {
+    gen: NameArgs<TokenStream>,
+}
+
+/// The inside of `duration(...)`.
+#[derive(StructMeta, Debug)]
+struct DurationArgs {
+    secs: Option<NameValue<TokenStream>>,
+}
+
+/// The inside of `char(...)`.
+#[derive(StructMeta, Debug)]
+struct CharArgs {
+    ascii: bool,
+    alphanumeric: bool,
+    /// An array of `RangeInclusive<char>`s.
+    ranges: Option<NameValue<TokenStream>>,
+}
+
+/// The inside of `try_gen(...)`: a generator, then maybe `or = EXPR`.
+#[derive(Debug)]
+struct TryGenArgs {
+    gen: TokenStream,
+    or: Option<TokenStream>,
+}
+
+impl Parse for TryGenArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // closures may have commas of their own
+        let gen = input.parse::<Expr>()?.into_token_stream();
+        let or = match input.parse::<Option<Comma>>()?.is_some() && !input.is_empty() {
+            true => {
+                let name = input.parse::<Ident>()?;
+                if name != "or" {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        "expected a fallback: `or = ...`",
+                    ));
+                }
+                input.parse::<Token![=]>()?;
+                Some(input.parse::<Expr>()?.into_token_stream())
+            }
+            false => None,
+        };
+        Ok(Self { gen, or })
+    }
+}
+
+/// One of `bound_for(T = "Bound + Other", ...)`.
+struct BoundFor {
+    param: Ident,
+    bounds: LitStr,
+}
+
+impl Parse for BoundFor {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let param = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let bounds = input.parse()?;
+        Ok(Self { param, bounds })
+    }
+}
+
+impl BoundFor {
+    fn predicate(&self) -> syn::Result<WherePredicate> {
+        let param = &self.param;
+        let bounds = self
+            .bounds
+            .parse_with(Punctuated::<TypeParamBound, Token![+]>::parse_separated_nonempty)?;
+        Ok(parse_quote!(#param: #bounds))
+    }
+}
+
+/// The inside of `repeat_with(...)`.
+#[derive(StructMeta, Debug)]
+struct RepeatWithArgs {
+    gen: NameArgs<TokenStream>,
+    len: NameArgs<TokenStream>,
+}
+
+impl Arg {
+    /// Combine with `other`, which was parsed from `attr`.
+    ///
+    /// `where` clauses accumulate.
+    fn merge(self, other: Self, attr: &Attribute) -> syn::Result<Self> {
+        let Arg {
+            names,
+            skip,
+            value,
+            r#where,
+            include_deprecated,
+            distinct,
+            finite,
+            finite_floats,
+            validate,
+            seed_field,
+            flatten,
+            no_shrink_target,
+            respect_serde_skip,
+            portable,
+            round_robin,
+            unbiased,
+            no_clone,
+            distinct_variants,
+            type_shrink,
+            instrument,
+            rare,
+            shrink_in_place,
+            with_gen,
+            order,
+            size,
+            exclude_if,
+            constructor,
+            proptest,
+            error_if_generic,
+        } = other;
+        Ok(Arg {
+            names: self.names.into_iter().chain(names).collect(),
+            skip: self.skip || skip,
+            value: match (self.value, value) {
+                (Some(_), Some(_)) => return Err(conflict(attr)),
+                (left, right) => left.or(right),
+            },
+            r#where: match (self.r#where, r#where) {
+                (Some(mut left), Some(right)) => {
+                    left.extend(right);
+                    Some(left)
+                }
+                (left, right) => left.or(right),
+            },
+            include_deprecated: self.include_deprecated || include_deprecated,
+            distinct: self.distinct || distinct,
+            finite: self.finite || finite,
+            finite_floats: self.finite_floats || finite_floats,
+            validate: self.validate.into_iter().chain(validate).collect(),
+            seed_field: only_one(self.seed_field, seed_field, "seed_field", attr)?,
+            flatten: self.flatten || flatten,
+            no_shrink_target: self.no_shrink_target || no_shrink_target,
+            respect_serde_skip: self.respect_serde_skip || respect_serde_skip,
+            portable: self.portable || portable,
+            round_robin: self.round_robin || round_robin,
+            unbiased: self.unbiased || unbiased,
+            no_clone: self.no_clone || no_clone,
+            distinct_variants: self.distinct_variants || distinct_variants,
+            type_shrink: self.type_shrink || type_shrink,
+            instrument: self.instrument || instrument,
+            rare: self.rare || rare,
+            shrink_in_place: self.shrink_in_place || shrink_in_place,
+            with_gen: only_one(self.with_gen, with_gen, "with_gen", attr)?,
+            order: only_one(self.order, order, "order", attr)?,
+            size: only_one(self.size, size, "size", attr)?,
+            exclude_if: only_one(self.exclude_if, exclude_if, "exclude_if", attr)?,
+            constructor: only_one(self.constructor, constructor, "constructor", attr)?,
+            proptest: self.proptest || proptest,
+            error_if_generic: self.error_if_generic || error_if_generic,
+        })
+    }
+}
+
+/// `left` or `right`, but not both, where `right` was parsed from `attr`.
+fn only_one<T>(
+    left: Option<T>,
+    right: Option<T>,
+    name: &str,
+    attr: &Attribute,
+) -> syn::Result<Option<T>> {
+    match (left, right) {
+        (Some(_), Some(_)) => Err(syn::Error::new_spanned(
+            attr,
+            format!("only one `{}` may be specified", name),
+        )),
+        (left, right) => Ok(left.or(right)),
+    }
+}
+
+/// Where an `#[arbitrary(...)]` attribute is.
+#[derive(Clone, Copy, PartialEq)]
+enum Location {
+    Item,
+    Variant,
+    Member,
+}
+
+const ITEM: &[Location] = &[Location::Item];
+const VARIANT: &[Location] = &[Location::Variant];
+const MEMBER: &[Location] = &[Location::Member];
+const ITEM_OR_MEMBER: &[Location] = &[Location::Item, Location::Member];
+
+/// Every argument, and where it's valid.
+///
+/// Messages list them in this order.
+const ARGS: &[(&str, &[Location])] = &[
+    ("gen", MEMBER),
+    ("gen_iter", MEMBER),
+    ("gen_fn", MEMBER),
+    ("try_gen", MEMBER),
+    ("default", MEMBER),
+    ("len", MEMBER),
+    ("byte_len", MEMBER),
+    ("range", MEMBER),
+    ("head", MEMBER),
+    ("tail_len", MEMBER),
+    ("repeat_with", MEMBER),
+    ("each", MEMBER),
+    ("system_time", MEMBER),
+    ("duration", MEMBER),
+    ("ipv4", MEMBER),
+    ("ipv6", MEMBER),
+    ("char", MEMBER),
+    ("distinct", MEMBER),
+    ("finite", MEMBER),
+    ("flatten", MEMBER),
+    ("type_shrink", MEMBER),
+    ("order", MEMBER),
+    ("where", ITEM_OR_MEMBER),
+    ("bound_for", ITEM_OR_MEMBER),
+    ("include_deprecated", ITEM),
+    ("finite_floats", ITEM),
+    ("validate", ITEM),
+    ("seed_field", ITEM),
+    ("respect_serde_skip", ITEM),
+    ("portable", ITEM),
+    ("round_robin", ITEM),
+    ("unbiased", ITEM),
+    ("no_clone", ITEM),
+    ("distinct_variants", ITEM),
+    ("instrument", ITEM),
+    ("shrink_in_place", ITEM),
+    ("with_gen", ITEM),
+    ("size", ITEM),
+    ("constructor", ITEM),
+    ("proptest", ITEM),
+    ("error_if_generic", ITEM),
+    ("skip", VARIANT),
+    ("no_shrink_target", VARIANT),
+    ("exclude_if", VARIANT),
+    ("rare", VARIANT),
+];
+
+/// Arguments that say how a member is generated, so only one may be given.
+const VALUE_ARGS: &[&str] = &[
+    "gen",
+    "gen_iter",
+    "gen_fn",
+    "try_gen",
+    "default",
+    "len",
+    "byte_len",
+    "range",
+    "system_time",
+    "duration",
+    "ipv4",
+    "ipv6",
+    "char",
+    "head",
+    "repeat_with",
+    "each",
+];
+
+/// `` `a`, `b` or `c` ``, joined with `last`.
+fn list<'a>(names: impl IntoIterator<Item = &'a str>, last: &str) -> String {
+    let names = names
+        .into_iter()
+        .map(|it| format!("`{}`", it))
+        .collect::<Vec<_>>();
+    match names.split_last() {
+        Some((only, [])) => only.clone(),
+        Some((end, rest)) => format!("{} {} {}", rest.join(", "), last, end),
+        None => String::new(),
+    }
+}
+
+/// More than one of the [`VALUE_ARGS`].
+fn conflict(tokens: impl ToTokens) -> syn::Error {
+    syn::Error::new_spanned(
+        tokens,
+        format!(
+            "only one of {} may be specified",
+            list(VALUE_ARGS.iter().copied(), "or")
+        ),
+    )
+}
+
+/// `name`, which excludes all the [`VALUE_ARGS`] and `others`.
+fn excludes_values(tokens: impl ToTokens, name: &str, others: &[&str]) -> syn::Error {
+    syn::Error::new_spanned(
+        tokens,
+        format!(
+            "`{}` can't be combined with {}",
+            name,
+            list(VALUE_ARGS.iter().chain(others).copied(), "or"),
+        ),
+    )
+}
+
+/// Reject the first argument in `arg` that isn't valid at `location`.
+fn check_location(arg: &Arg, location: Location) -> syn::Result<()> {
+    let valid = |name: &str| {
+        ARGS.iter()
+            .any(|(it, locations)| *it == name && locations.contains(&location))
+    };
+    match arg.names.iter().find(|it| !valid(&it.to_string())) {
+        Some(name) => Err(syn::Error::new(
+            name.span(),
+            format!(
+                "only {} are valid for {}",
+                list(
+                    ARGS.iter()
+                        .filter(|(_, locations)| locations.contains(&location))
+                        .map(|(it, _)| *it),
+                    "and",
+                ),
+                match location {
+                    Location::Item => "items",
+                    Location::Variant => "enum variants",
+                    Location::Member => "members",
+                },
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Arguments which structmeta would reject confusingly if they're missing their parentheses.
+const REQUIRES_ARGS: &[(&str, &str)] = &[
+    (
+        "gen",
+        "`gen` requires a generator: `gen(|g| ...)` or `gen = PATH`",
+    ),
+    (
+        "gen_iter",
+        "`gen_iter` requires a generator: `gen_iter(|g| ...)`",
+    ),
+    (
+        "where",
+        "`where` requires predicates: `where(T: Arbitrary)`",
+    ),
+];
+
+/// `name = EXPR` arguments.
+#[derive(Default)]
+struct NameValues {
+    /// `default = EXPR`
+    default: Option<Expr>,
+    /// `len = N`
+    len: Option<Expr>,
+    /// `gen_fn = STRATEGY`
+    gen_fn: Option<Expr>,
+    /// `gen = GENERATOR`, e.g. a path to a `const` fn pointer
+    gen: Option<Expr>,
+    /// `order = N`
+    order: Option<Expr>,
+    /// `size = N`
+    size: Option<Expr>,
+}
+
+/// Take [`NameValues`] out of `input`, returning the other arguments,
+/// and the name of every argument.
+///
+/// structmeta can't parse both forms of the same argument,
+/// and can't hold an [`Expr`] without syn's `extra-traits`.
+fn split_name_values(input: ParseStream) -> syn::Result<(TokenStream, NameValues, Vec<Ident>)> {
+    let mut rest = TokenStream::new();
+    let mut name_values = NameValues::default();
+    let mut names = vec![];
+    while !input.is_empty() {
+        let fork = input.fork();
+        let name = Ident::parse_any(&fork);
+        if let Ok(name) = &name {
+            names.push(name.clone());
+        }
+        if let (Ok(name), true) = (name, fork.peek(Token![=])) {
+            let slot = match name.to_string().as_str() {
+                "default" => Some(&mut name_values.default),
+                "len" => Some(&mut name_values.len),
+                "gen_fn" => Some(&mut name_values.gen_fn),
+                "gen" => Some(&mut name_values.gen),
+                "order" => Some(&mut name_values.order),
+                "size" => Some(&mut name_values.size),
+                _ => None,
+            };
+            if let Some(slot) = slot {
+                Ident::parse_any(input)?;
+                input.parse::<Token![=]>()?;
+                if slot.replace(input.parse::<Expr>()?).is_some() {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!("only one `{} = ...` may be specified", name),
+                    ));
+                }
+                if !input.is_empty() {
+                    input.parse::<Token![,]>()?;
+                }
+                continue;
+            }
+        }
+        // the rest of this argument, and its comma
+        while !input.is_empty() {
+            let tt = input.parse::<TokenTree>()?;
+            let comma = matches!(&tt, TokenTree::Punct(punct) if punct.as_char() == ',');
+            rest.extend([tt]);
+            if comma {
+                break;
+            }
+        }
+    }
+    Ok((rest, name_values, names))
+}
+
+/// Look for e.g. `gen` on its own in `input`, without consuming it.
+fn bare_arg(input: ParseStream) -> Option<syn::Error> {
+    let fork = input.fork();
+    // at the start of an argument
+    let mut start = true;
+    while let Ok(tt) = fork.parse::<TokenTree>() {
+        match &tt {
+            TokenTree::Ident(ident) if start && (fork.is_empty() || fork.peek(Token![,])) => {
+                if let Some((_, message)) = REQUIRES_ARGS.iter().find(|(name, _)| ident == name) {
+                    return Some(syn::Error::new(ident.span(), message));
+                }
+                start = false;
+            }
+            TokenTree::Punct(punct) if punct.as_char() == ',' => start = true,
+            _ => start = false,
+        }
+    }
+    None
+}
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if let Some(e) = bare_arg(input) {
+            return Err(e);
+        }
+        let mut hint = syn::Error::new(
+            input.span(),
+            format!(
+                "expected one of {}",
+                list(ARGS.iter().map(|(it, _)| *it), "or")
+            ),
+        );
+        let (rest, name_values, names) = split_name_values(input)?;
+        if names.is_empty() {
+            return Err(hint);
+        }
+        if let Some(second) = names
+            .iter()
+            .filter(|it| VALUE_ARGS.contains(&it.to_string().as_str()))
+            .nth(1)
+        {
+            return Err(conflict(second));
+        }
+        let AttrArgs {
+            gen,
+            gen_iter,
+            skip,
+            default,
+            len,
+            byte_len,
+            range,
+            r#where,
+            bound_for,
+            include_deprecated,
+            distinct,
+            finite,
+            finite_floats,
+            system_time,
+            duration,
+            ipv4,
+            ipv6,
+            char,
+            try_gen,
+            validate,
+            head,
+            tail_len,
+            repeat_with,
+            each,
+            seed_field,
+            exclude_if,
+            constructor,
+            flatten,
+            no_shrink_target,
+            respect_serde_skip,
+            portable,
+            round_robin,
+            unbiased,
+            no_clone,
+            distinct_variants,
+            type_shrink,
+            instrument,
+            rare,
+            shrink_in_place,
+            with_gen,
+            proptest,
+            error_if_generic,
+        } = match syn::parse2::<AttrArgs>(rest) {
+            Ok(it) => it,
+            // inner error
+            Err(e) => {
+                hint.combine(e);
+                return Err(hint);
+            }
+        };
+        if let (None, Some(NameArgs { name_span, .. })) = (&head, &tail_len) {
+            return Err(syn::Error::new(*name_span, "`tail_len` requires `head`"));
+        }
+        let NameValues {
+            default: default_expr,
+            len: exact_len,
+            gen_fn,
+            gen: gen_path,
+            order,
+            size,
+        } = name_values;
+        // at most one of these, from the check above
+        let value = [
+            gen.map(|NameArgs { name_span: _, args }| Ok(Value::Gen(args))),
+            gen_path.map(|it| Ok(Value::Gen(it.into_token_stream()))),
+            gen_iter.map(|NameArgs { name_span: _, args }| Ok(Value::GenIter(args))),
+            gen_fn.map(|it| Ok(Value::Strategy(it))),
+            try_gen.map(|NameArgs { name_span: _, args }| {
+                Ok(Value::TryGen {
+                    gen: args.gen,
+                    or: args.or.map(syn::parse2).transpose()?,
+                })
+            }),
+            default.then(|| Ok(Value::Default)),
+            default_expr.map(|it| Ok(Value::DefaultExpr(it))),
+            len.map(|NameArgs { name_span: _, args }| syn::parse2(args).map(Value::Len)),
+            // exactly `n`, which must be a `usize`
+            exact_len.map(|n| {
+                Ok(Value::Len(parse_quote!({
+                    let len: ::core::primitive::usize = #n;
+                    len..=len
+                })))
+            }),
+            byte_len.map(|NameArgs { name_span: _, args }| syn::parse2(args).map(Value::ByteLen)),
+            range.map(|NameArgs { name_span: _, args }| syn::parse2(args).map(Value::Range)),
+            system_time.then(|| Ok(Value::SystemTime)),
+            duration.map(|NameArgs { name_span: _, args }| {
+                Ok(Value::Duration {
+                    secs: args
+                        .and_then(|it| it.secs)
+                        .map(|it| syn::parse2(it.value))
+                        .transpose()?,
+                })
+            }),
+            ipv4.then(|| Ok(Value::Ip { v6: false })),
+            ipv6.then(|| Ok(Value::Ip { v6: true })),
+            char.map(|NameArgs { name_span, args }| char_ranges(name_span, args).map(Value::Char)),
+            head.map(|NameArgs { name_span: _, args }| {
+                Ok(Value::HeadTail {
+                    head: args.gen.args,
+                    tail_len: match tail_len {
+                        Some(NameArgs { name_span: _, args }) => syn::parse2(args)?,
+                        None => parse_quote!(..),
+                    },
+                })
+            }),
+            // sugar for `len` with a custom element
+            repeat_with.map(|NameArgs { name_span: _, args }| {
+                Ok(Value::RepeatWith {
+                    gen: args.gen.args,
+                    len: syn::parse2(args.len.args)?,
+                })
+            }),
+            each.map(|NameArgs { name_span: _, args }| Ok(Value::Each(args))),
+        ]
+        .into_iter()
+        .flatten()
+        .next()
+        .transpose()?;
+        let order = order
+            .map(|it| match it {
+                Expr::Lit(ExprLit {
+                    attrs: _,
+                    lit: Lit::Int(int),
+                }) => int.base10_parse::<u32>(),
+                other => Err(syn::Error::new_spanned(
+                    other,
+                    "`order` must be an integer literal",
+                )),
+            })
+            .transpose()?;
+        let with_gen = with_gen
+            .map(|NameArgs { name_span: _, args }| syn::parse2::<Type>(args))
+            .transpose()?;
+        let r#where = r#where
+            .map(|NameArgs { name_span: _, args }| {
+                Punctuated::<WherePredicate, Comma>::parse_terminated.parse2(args)
+            })
+            .transpose()?;
+        let bound_for = bound_for
+            .map(|NameArgs { name_span: _, args }| {
+                Punctuated::<BoundFor, Comma>::parse_terminated
+                    .parse2(args)?
+                    .iter()
+                    .map(BoundFor::predicate)
+                    .collect::<syn::Result<Vec<_>>>()
+            })
+            .transpose()?;
+        // sugar for `where`
+        let r#where = match (r#where, bound_for) {
+            (None, None) => None,
+            (left, right) => Some(
+                left.into_iter()
+                    .flatten()
+                    .chain(right.into_iter().flatten())
+                    .collect(),
+            ),
+        };
+        let validate = Vec::from_iter(validate.map(|NameArgs { name_span: _, args }| args));
+        let exclude_if = exclude_if
+            .map(
+                |NameValue {
+                     name_span: _,
+                     value,
+                 }| {
+                    syn::parse2::<Path>(value.clone()).map_err(|_| {
+                        syn::Error::new_spanned(
+                            value,
+                            "`exclude_if` must be a path to a `fn() -> bool`",
+                        )
+                    })
+                },
+            )
+            .transpose()?;
+        let constructor = constructor
+            .map(
+                |NameValue {
+                     name_span: _,
+                     value,
+                 }| {
+                    syn::parse2::<Path>(value.clone()).map_err(|_| {
+                        syn::Error::new_spanned(
+                            value,
+                            "`constructor` must be a path to a function returning `Self`",
+                        )
+                    })
+                },
+            )
+            .transpose()?;
+        Ok(Arg {
+            names,
+            skip,
+            value,
+            r#where,
+            include_deprecated,
+            distinct,
+            finite,
+            finite_floats,
+            validate,
+            seed_field: seed_field.map(
+                |NameValue {
+                     name_span: _,
+                     value,
+                 }| value,
+            ),
+            flatten,
+            no_shrink_target,
+            respect_serde_skip,
+            portable,
+            round_robin,
+            unbiased,
+            no_clone,
+            distinct_variants,
+            type_shrink,
+            instrument,
+            rare,
+            shrink_in_place,
+            with_gen,
+            order,
+            size,
+            exclude_if,
+            constructor,
+            proptest,
+            error_if_generic,
+        })
+    }
+}
+
+/// Merge all the `#[arbitrary(...)]` attributes.
+fn get_args(attrs: &[Attribute]) -> syn::Result<Arg> {
+    attrs
+        .iter()
+        .filter(|it| it.path().is_ident("arbitrary"))
+        .try_fold(
+            Arg::default(),
+            |acc,
+             attr @ Attribute {
+                 pound_token: _,
+                 style,
+                 bracket_token: _,
+                 meta: _,
+             }| {
+                match style {
+                    AttrStyle::Outer => acc.merge(attr.parse_args()?, attr),
+                    AttrStyle::Inner(_) => Err(syn::Error::new_spanned(
+                        attr,
+                        "only outer attributes are supported: `#[arbitrary(...)]`",
+                    )),
+                }
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use structmeta::NameArgs;
+
+    #[test]
+    fn readme() {
+        assert!(
+            std::process::Command::new("cargo")
+                .args(["rdme", "--check"])
+                .output()
+                .expect("couldn't run `cargo rdme`")
+                .status
+                .success(),
+            "README.md is out of date - bless the new version by running `cargo rdme`"
+        )
+    }
+
+    #[test]
+    fn attr_args() {
+        assert_eq!(
+            AttrArgs {
+                skip: true,
+                ..Default::default()
+            },
+            parse_quote!(skip),
+        );
+        assert_eq!(
+            AttrArgs {
+                default: true,
+                ..Default::default()
+            },
+            parse_quote!(default),
+        );
+        assert_eq!(
+            AttrArgs {
+                gen: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: quote!(some_fn)
+                }),
+                ..Default::default()
+            },
+            parse_quote!(gen(some_fn)),
+        );
+        assert_eq!(
+            AttrArgs {
+                gen_iter: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: quote!(|_| 0..3)
+                }),
+                ..Default::default()
+            },
+            parse_quote!(gen_iter(|_| 0..3)),
+        );
+        assert_eq!(
+            AttrArgs {
+                r#where: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: quote!(foo)
+                }),
+                ..Default::default()
+            },
+            parse_quote!(where(foo)),
+        );
+        assert_eq!(
+            AttrArgs {
+                len: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: quote!(1..=3)
+                }),
+                ..Default::default()
+            },
+            parse_quote!(len(1..=3)),
+        );
+        assert_eq!(
+            AttrArgs {
+                bound_for: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: quote!(T = "Arbitrary + Clone")
+                }),
+                ..Default::default()
+            },
+            parse_quote!(bound_for(T = "Arbitrary + Clone")),
+        );
+        assert_eq!(
+            AttrArgs {
+                exclude_if: Some(NameValue {
+                    name_span: Span::call_site(),
+                    value: quote!(is_offline)
+                }),
+                ..Default::default()
+            },
+            parse_quote!(exclude_if = is_offline),
+        );
+        assert_eq!(
+            AttrArgs {
+                constructor: Some(NameValue {
+                    name_span: Span::call_site(),
+                    value: quote!(Yak::from_parts)
+                }),
+                ..Default::default()
+            },
+            parse_quote!(constructor = Yak::from_parts),
+        );
+        assert_eq!(
+            AttrArgs {
+                byte_len: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: quote!(..=8)
+                }),
+                ..Default::default()
+            },
+            parse_quote!(byte_len(..=8)),
+        );
+        assert_eq!(
+            AttrArgs {
+                char: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: CharArgs {
+                        ascii: false,
+                        alphanumeric: false,
+                        ranges: Some(NameValue {
+                            name_span: Span::call_site(),
+                            value: quote!(['a'..='f'])
+                        }),
+                    }
+                }),
+                ..Default::default()
+            },
+            parse_quote!(char(ranges = ['a'..='f'])),
+        );
+        assert_eq!(
+            AttrArgs {
+                each: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: quote!(range(0..=9))
+                }),
+                ..Default::default()
+            },
+            parse_quote!(each(range(0..=9))),
+        );
+        assert_eq!(
+            AttrArgs {
+                range: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: quote!(1..=65535)
+                }),
+                ..Default::default()
+            },
+            parse_quote!(range(1..=65535)),
+        );
+        assert_eq!(
+            AttrArgs {
+                include_deprecated: true,
+                ..Default::default()
+            },
+            parse_quote!(include_deprecated),
+        );
+        assert_eq!(
+            AttrArgs {
+                distinct: true,
+                ..Default::default()
+            },
+            parse_quote!(distinct),
+        );
+        assert_eq!(
+            AttrArgs {
+                finite: true,
+                finite_floats: true,
+                ..Default::default()
+            },
+            parse_quote!(finite, finite_floats),
+        );
+        assert_eq!(
+            AttrArgs {
+                system_time: true,
+                ..Default::default()
+            },
+            parse_quote!(system_time),
+        );
+        assert_eq!(
+            AttrArgs {
+                duration: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: None,
+                }),
+                ..Default::default()
+            },
+            parse_quote!(duration),
+        );
+        assert_eq!(
+            AttrArgs {
+                duration: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: Some(DurationArgs {
+                        secs: Some(NameValue {
+                            name_span: Span::call_site(),
+                            value: quote!(0..=3600),
+                        }),
+                    }),
+                }),
+                ..Default::default()
+            },
+            parse_quote!(duration(secs = 0..=3600)),
+        );
+        assert_eq!(
+            AttrArgs {
+                try_gen: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: TryGenArgs {
+                        gen: quote!(|g, built| build(g, built)),
+                        or: Some(quote!(Yak::default())),
+                    },
+                }),
+                ..Default::default()
+            },
+            parse_quote!(try_gen(|g, built| build(g, built), or = Yak::default())),
+        );
+        assert_eq!(
+            AttrArgs {
+                validate: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: quote!(|it: &Self| it.is_consistent()),
+                }),
+                ..Default::default()
+            },
+            parse_quote!(validate(|it: &Self| it.is_consistent())),
+        );
+        assert_eq!(
+            AttrArgs {
+                head: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: HeadArgs {
+                        gen: NameArgs {
+                            name_span: Span::call_site(),
+                            args: quote!(|_| 1),
+                        },
+                    },
+                }),
+                tail_len: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: quote!(0..=4),
+                }),
+                ..Default::default()
+            },
+            parse_quote!(head(gen(|_| 1)), tail_len(0..=4)),
+        );
+        assert_eq!(
+            AttrArgs {
+                repeat_with: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: RepeatWithArgs {
+                        gen: NameArgs {
+                            name_span: Span::call_site(),
+                            args: quote!(|g| Id::fresh(g)),
+                        },
+                        len: NameArgs {
+                            name_span: Span::call_site(),
+                            args: quote!(1..=10),
+                        },
+                    },
+                }),
+                ..Default::default()
+            },
+            parse_quote!(repeat_with(gen(|g| Id::fresh(g)), len(1..=10))),
+        );
+        assert_eq!(
+            AttrArgs {
+                seed_field: Some(NameValue {
+                    name_span: Span::call_site(),
+                    value: parse_quote!(rng_seed),
+                }),
+                ..Default::default()
+            },
+            parse_quote!(seed_field = rng_seed),
+        );
+        assert_eq!(
+            AttrArgs {
+                flatten: true,
+                ..Default::default()
+            },
+            parse_quote!(flatten),
+        );
+        assert_eq!(
+            AttrArgs {
+                no_shrink_target: true,
+                ..Default::default()
+            },
+            parse_quote!(no_shrink_target),
+        );
+        assert_eq!(
+            AttrArgs {
+                respect_serde_skip: true,
+                ..Default::default()
+            },
+            parse_quote!(respect_serde_skip),
+        );
+        assert_eq!(
+            AttrArgs {
+                portable: true,
+                ..Default::default()
+            },
+            parse_quote!(portable),
+        );
+        assert_eq!(
+            AttrArgs {
+                round_robin: true,
+                ..Default::default()
+            },
+            parse_quote!(round_robin),
+        );
+        assert_eq!(
+            AttrArgs {
+                unbiased: true,
+                ..Default::default()
+            },
+            parse_quote!(unbiased),
+        );
+        assert_eq!(
+            AttrArgs {
+                ipv6: true,
+                ..Default::default()
+            },
+            parse_quote!(ipv6),
+        );
+        assert_eq!(
+            AttrArgs {
+                no_clone: true,
+                ..Default::default()
+            },
+            parse_quote!(no_clone),
+        );
+        assert_eq!(
+            AttrArgs {
+                distinct_variants: true,
+                ..Default::default()
+            },
+            parse_quote!(distinct_variants),
+        );
+        assert_eq!(
+            AttrArgs {
+                type_shrink: true,
+                ..Default::default()
+            },
+            parse_quote!(type_shrink),
+        );
+        assert_eq!(
+            AttrArgs {
+                instrument: true,
+                ..Default::default()
+            },
+            parse_quote!(instrument),
+        );
+        assert_eq!(
+            AttrArgs {
+                rare: true,
+                ..Default::default()
+            },
+            parse_quote!(rare),
+        );
+        assert_eq!(
+            AttrArgs {
+                shrink_in_place: true,
+                ..Default::default()
+            },
+            parse_quote!(shrink_in_place),
+        );
+        assert_eq!(
+            AttrArgs {
+                with_gen: Some(NameArgs {
+                    name_span: Span::call_site(),
+                    args: quote!(Budgeted<'_>)
+                }),
+                ..Default::default()
+            },
+            parse_quote!(with_gen(Budgeted<'_>)),
+        );
+    }
+
+    #[test]
+    fn name_values() {
+        let arg: Arg = parse_quote!(default = 1 + 2, distinct);
+        assert!(matches!(arg.value, Some(Value::DefaultExpr(_))));
+        assert!(arg.distinct);
+        let arg: Arg = parse_quote!(distinct, default = Foo::<A, B>::new());
+        assert!(matches!(arg.value, Some(Value::DefaultExpr(_))));
+        assert!(syn::parse2::<Arg>(quote!(default, default = 1)).is_err());
+        assert!(syn::parse2::<Arg>(quote!(default = 1, default = 2)).is_err());
+        let arg: Arg = parse_quote!(len = 16, distinct);
+        assert!(matches!(arg.value, Some(Value::Len(_))));
+        assert!(arg.distinct);
+        assert!(syn::parse2::<Arg>(quote!(len = 16, len(1..))).is_err());
+        assert!(syn::parse2::<Arg>(quote!(len = 16, default = vec![])).is_err());
+        let arg: Arg = parse_quote!(gen_fn = strategies::PERCENT);
+        assert!(matches!(arg.value, Some(Value::Strategy(_))));
+        assert!(syn::parse2::<Arg>(quote!(gen_fn = PERCENT, gen(|_| 1))).is_err());
+        let arg: Arg = parse_quote!(gen = generators::SHEAR, distinct);
+        assert!(matches!(arg.value, Some(Value::Gen(_))));
+        assert!(syn::parse2::<Arg>(quote!(gen = SHEAR, gen(|_| 1))).is_err());
+        let arg: Arg = parse_quote!(order = 2, gen(|_| 1));
+        assert_eq!(arg.order, Some(2));
+        assert!(matches!(arg.value, Some(Value::Gen(_))));
+        assert!(syn::parse2::<Arg>(quote!(order = -1)).is_err());
+        let arg: Arg = parse_quote!(size = 10, no_clone);
+        assert!(arg.size.is_some());
+        assert!(arg.no_clone);
+    }
 
-fn expand_arbitrary(input: DeriveInput) -> syn::Result<TokenStream> {
-    let struct_name = input.ident.clone();
-    let generics = input.generics.clone();
-    let gen_name = &quote!(g);
-    let predicates = match get_one_arg(&input.attrs, input.span())? {
-        Some(Arg::Where(preds)) => preds,
-        None => Punctuated::new(),
-        Some(Arg::Default | Arg::Gen(_) | Arg::Skip) => {
-            return Err(syn::Error::new(
-                input.span(),
-                "only `where` is valid for items",
-            ))
+    #[test]
+    fn args_table() {
+        for (ix, (name, _)) in ARGS.iter().enumerate() {
+            assert!(!ARGS[..ix].iter().any(|(it, _)| it == name), "{}", name);
         }
-    };
-    let where_clause = WhereClause {
-        where_token: Token![where](Span::call_site()),
-        predicates,
-    };
-
-    let ctor = match input.data {
-        syn::Data::Struct(DataStruct { fields, .. }) => expr_struct(
-            path_of_idents([struct_name.clone()]),
-            field_values(fields, gen_name)?,
-        )
-        .into_token_stream(),
-        syn::Data::Enum(DataEnum { variants, .. }) => {
-            let span = variants.span();
-            let variant_ctors = variants
-                .into_iter()
-                .filter_map(
-                    |Variant {
-                         attrs,
-                         ident,
-                         fields,
-                         ..
-                     }| match get_one_arg(&attrs, span) {
-                        Ok(None) => match field_values(fields, gen_name) {
-                            Ok(fields) => {
-                                let variant_ctor = expr_struct(
-                                    path_of_idents([struct_name.clone(), ident]),
-                                    fields,
-                                );
-                                Some(Ok(variant_ctor))
-                            }
-                            Err(e) => Some(Err(e)),
-                        },
-                        Ok(Some(Arg::Skip)) => None,
-                        Ok(Some(Arg::Gen(_) | Arg::Default | Arg::Where(_))) => {
-                            Some(Err(syn::Error::new(
-                                span,
-                                "`gen`, `default` and `where` are not valid for enum variants", // TODO: probably could be
-                            )))
-                        }
-                        Err(e) => Some(Err(e)),
-                    },
-                )
-                .collect::<Result<Vec<_>, _>>()?;
-            quote!(
-                let options = [ #(#variant_ctors,)* ];
-                #gen_name.choose(options.as_slice()).expect("no variants to choose from").clone()
-            )
+        for name in VALUE_ARGS {
+            assert!(ARGS.contains(&(name, MEMBER)), "{}", name);
         }
-        syn::Data::Union(_) => {
-            return Err(syn::Error::new_spanned(
-                input,
-                "#[derive(Arbitrary)] is not supported on `union`s",
-            ))
+        #[cfg(feature = "proptest")]
+        for name in proptest::UNSUPPORTED {
+            assert!(ARGS.iter().any(|(it, _)| it == name), "{}", name);
         }
-    };
+        let arg: Arg = parse_quote!(where(T: Arbitrary), distinct);
+        assert_eq!(arg.names, ["where", "distinct"]);
+        assert!(check_location(&arg, Location::Member).is_ok());
+        assert!(check_location(&arg, Location::Item).is_err());
+        let arg: Arg = parse_quote!(skip, rare);
+        assert!(check_location(&arg, Location::Variant).is_ok());
+        let arg: Arg = parse_quote!(skip, size = 10);
+        assert!(check_location(&arg, Location::Variant).is_err());
+    }
 
-    Ok(quote! {
-        impl #generics ::quickcheck::Arbitrary for #struct_name #generics
-            #where_clause
-        {
-            fn arbitrary(#gen_name: &mut ::quickcheck::Gen) -> Self {
-                #ctor
-            }
+    #[test]
+    fn recursive_options() {
+        let node = Ident::new("Node", Span::call_site());
+        for ty in [
+            quote!(Option<Box<Self>>),
+            quote!(Option<Box<Node>>),
+            quote!(::std::option::Option<Box<Node>>),
+        ] {
+            assert!(is_recursive_option(&parse_quote!(#ty), &node), "{}", ty);
         }
-    })
-}
+        for ty in [
+            quote!(Option<Box<other::Node>>),
+            quote!(Option<Box<::Node>>),
+            quote!(Option<Box<Self::Node>>),
+            quote!(Option<Box<Tree>>),
+            quote!(Option<Node>),
+        ] {
+            assert!(!is_recursive_option(&parse_quote!(#ty), &node), "{}", ty);
+        }
+    }
 
-fn field_values(
-    fields: Fields,
-    gen_name: &TokenStream,
-) -> syn::Result<Punctuated<FieldValue, Comma>> {
-    fields
-        .into_iter()
-        .enumerate()
-        .map(|(ix, field)| {
-            let value = match get_one_arg(&field.attrs, field.span())? {
-                Some(Arg::Skip | Arg::Where(_)) => {
-                    return Err(syn::Error::new_spanned(
-                        field,
-                        "`skip` and `where` are not valid for members",
-                    ))
-                }
-                Some(Arg::Gen(custom)) => {
-                    let ty = field.ty;
-                    quote! {
-                        (
-                            ( #custom ) as ( fn(&mut ::quickcheck::Gen) -> #ty )
-                        ) // cast to fn pointer
-                        (&mut *#gen_name) // call it
-                    }
-                }
-                Some(Arg::Default) => {
-                    quote!(::core::default::Default::default())
-                }
-                None => quote!(::quickcheck::Arbitrary::arbitrary(#gen_name)),
-            };
-            Ok(FieldValue {
-                attrs: vec![],
-                member: match field.ident {
-                    Some(name) => Member::Named(name),
-                    None => Member::Unnamed(Index::from(ix)),
-                },
-                colon_token: Some(Colon::default()),
-                expr: Expr::Verbatim(value),
-            })
+    #[test]
+    fn single_variant_enum_skips_selection() {
+        let expanded = expand_arbitrary(parse_quote! {
+            enum Wrapper {
+                Only(u8),
+            }
         })
-        .collect()
-}
-
-fn expr_struct(path: Path, field_values: Punctuated<FieldValue, Comma>) -> ExprStruct {
-    ExprStruct {
-        attrs: vec![],
-        qself: None,
-        path,
-        brace_token: Brace::default(),
-        fields: field_values,
-        dot2_token: None,
-        rest: None,
+        .unwrap()
+        .to_string();
+        assert!(!expanded.contains("choose"), "{}", expanded);
+        assert!(expanded.contains("fn shrink"), "{}", expanded);
     }
-}
 
-fn path_of_idents(idents: impl IntoIterator<Item = Ident>) -> Path {
-    Path {
-        leading_colon: None,
-        segments: Punctuated::from_iter(idents.into_iter().map(|ident| PathSegment {
-            ident,
-            arguments: syn::PathArguments::None,
-        })),
+    #[test]
+    fn portable_selection_draws_u32() {
+        let expanded = expand_arbitrary(parse_quote! {
+            #[arbitrary(portable)]
+            enum Direction {
+                North,
+                South(u8),
+            }
+        })
+        .unwrap()
+        .to_string();
+        assert!(!expanded.contains("choose"), "{}", expanded);
+        let draw = quote! {
+            let draw = <::core::primitive::u32 as ::quickcheck::Arbitrary>::arbitrary(g);
+            if (2u32..4294967294u32).contains(&draw) {
+                break (draw % 2u32) as ::core::primitive::usize;
+            }
+        };
+        assert!(expanded.contains(&draw.to_string()), "{}", expanded);
     }
-}
-
-#[derive(Clone)]
-enum Arg {
-    Skip,
-    Gen(TokenStream),
-    Default,
-    Where(Punctuated<WherePredicate, Comma>),
-}
 
-#[derive(StructMeta, Debug, Default)]
-struct AttrArgs {
-    gen: Option<NameArgs<TokenStream>>,
-    skip: bool,
-    default: bool,
-    r#where: Option<NameArgs<TokenStream>>,
-}
-
-impl Parse for Arg {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let mut hint = syn::Error::new(
-            input.span(),
-            "expected one of  `gen`, `default`, `where` or `skip`",
-        );
-        match AttrArgs::parse(input) {
-            // inner error
-            Err(e) => {
-                hint.combine(e);
-                Err(hint)
-            }
-            // nothing
-            Ok(AttrArgs {
-                gen: None,
-                r#where: None,
-                skip: false,
-                default: false,
-            }) => Err(hint),
-            // just `skip`
-            Ok(AttrArgs {
-                skip: true,
+    #[test]
+    fn fieldless_enum_builds_directly() {
+        let expanded = expand_arbitrary(parse_quote! {
+            enum Direction {
+                North,
+                South,
+            }
+        })
+        .unwrap()
+        .to_string();
+        let (arbitrary, _) = expanded.split_once("fn shrink").unwrap();
+        assert!(!arbitrary.contains("clone"), "{}", arbitrary);
+        assert!(!arbitrary.contains("options"), "{}", arbitrary);
+    }
 
-                gen: None,
-                default: false,
-                r#where: None,
-            }) => Ok(Arg::Skip),
-            // just `gen`
-            Ok(AttrArgs {
-                gen: Some(NameArgs { name_span: _, args }),
-
-                r#where: None,
-                skip: false,
-                default: false,
-            }) => Ok(Arg::Gen(args)),
-
-            // just `where`
-            Ok(AttrArgs {
-                r#where: Some(NameArgs { name_span: _, args }),
-
-                gen: None,
-                skip: false,
-                default: false,
-            }) => Ok(Arg::Where(Punctuated::parse_terminated.parse2(args)?)), // just `default`
-            Ok(AttrArgs {
-                default: true,
+    #[test]
+    fn gen_keeps_trait_object_bounds() {
+        let expanded = expand_arbitrary(parse_quote! {
+            struct Barber {
+                #[arbitrary(gen(|_| Box::new(Razor)))]
+                razor: Box<dyn Shaves + Send + Sync + 'static>,
+            }
+        })
+        .unwrap()
+        .to_string();
+        let ty = quote!(fn(&mut ::quickcheck::Gen) -> Box<dyn Shaves + Send + Sync + 'static>);
+        assert!(expanded.contains(&ty.to_string()), "{}", expanded);
+    }
 
-                r#where: None,
-                gen: None,
-                skip: false,
-            }) => Ok(Arg::Default),
-            // some combination of arguments
-            Ok(AttrArgs { .. }) => Err(hint),
+    #[test]
+    fn gen_keeps_static_lifetimes() {
+        let expanded = expand_arbitrary(parse_quote! {
+            struct Yak {
+                #[arbitrary(gen(|_| Cow::Borrowed("yak")))]
+                name: Cow<'static, str>,
+                #[arbitrary(gen(|_| "yak"))]
+                nickname: &'static str,
+            }
+        })
+        .unwrap()
+        .to_string();
+        for ty in [
+            quote!(let generate: fn(&mut ::quickcheck::Gen) -> Cow<'static, str>),
+            quote!(let generate: fn(&mut ::quickcheck::Gen) -> &'static str),
+        ] {
+            assert!(expanded.contains(&ty.to_string()), "{}", expanded);
         }
     }
-}
 
-fn get_one_arg(attrs: &[Attribute], parent_span: Span) -> syn::Result<Option<Arg>> {
-    let configs = attrs
-        .iter()
-        .filter(|it| it.path().is_ident("arbitrary"))
-        .map(
-            |attr @ Attribute {
-                 pound_token: _,
-                 style,
-                 bracket_token: _,
-                 meta: _,
-             }| {
-                match style {
-                    AttrStyle::Outer => attr.parse_args::<Arg>(),
-                    AttrStyle::Inner(_) => Err(syn::Error::new_spanned(
-                        attr,
-                        "only outer attributes are supported: `#[arbitrary(...)]`",
-                    )),
+    #[test]
+    fn plain_structs_generate_without_branches() {
+        let expanded = expand_arbitrary(parse_quote! {
+            struct Yak {
+                name: String,
+                #[arbitrary(gen(|_| 7))]
+                age: u8,
+                #[arbitrary(default)]
+                herd: Vec<u8>,
+            }
+        })
+        .unwrap();
+        assert!(expanded
+            .to_string()
+            .starts_with(&quote!(#[automatically_derived]).to_string()));
+        fn flatten(tokens: TokenStream, into: &mut Vec<proc_macro2::TokenTree>) {
+            for tt in tokens {
+                match tt {
+                    proc_macro2::TokenTree::Group(group) => flatten(group.stream(), into),
+                    tt => into.push(tt),
                 }
-            },
-        )
-        .collect::<Result<Vec<_>, _>>()?;
-    match configs.as_slice() {
-        [] => Ok(None),
-        [one] => Ok(Some(one.clone())),
-        _too_many => Err(syn::Error::new(
-            parent_span,
-            "`#[arbitrary(...)]` can only be specified once",
-        )),
+            }
+        }
+        let mut tokens = vec![];
+        flatten(expanded, &mut tokens);
+        let body = tokens
+            .iter()
+            .skip_while(|it| it.to_string() != "arbitrary")
+            .take_while(|it| it.to_string() != "shrink")
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        for branch in ["match", "if", "loop", "while", "for"] {
+            assert!(!body.iter().any(|it| it == branch), "{:?}", body);
+        }
+        // just the user's `|_| 7`
+        assert_eq!(body.iter().filter(|it| *it == "|").count(), 2, "{:?}", body);
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
-    use structmeta::NameArgs;
-    use syn::parse_quote;
+    #[test]
+    fn inferred_bounds_are_added_once() {
+        let expanded = expand_arbitrary(parse_quote! {
+            struct Yak<T> {
+                a: Cell<T>,
+                b: Cell<T>,
+                c: RefCell<T>,
+            }
+        })
+        .unwrap()
+        .to_string();
+        let (header, _) = expanded.split_once("fn arbitrary").unwrap();
+        let bound = quote!(T: ::quickcheck::Arbitrary).to_string();
+        assert_eq!(header.matches(&bound).count(), 1, "{}", header);
+    }
 
     #[test]
-    fn readme() {
+    fn constructors_keep_delimiters() {
+        let expanded = expand_arbitrary(parse_quote! {
+            enum Yak {
+                Unit,
+                Tuple(u8),
+                Struct { name: u8 },
+            }
+        })
+        .unwrap()
+        .to_string();
+        for ctor in [
+            quote!(Yak::Unit,),
+            quote!(Yak::Tuple(::quickcheck::Arbitrary::arbitrary(g))),
+            quote!(Yak::Struct {
+                name: ::quickcheck::Arbitrary::arbitrary(g)
+            }),
+        ] {
+            assert!(expanded.contains(&ctor.to_string()), "{}", expanded);
+        }
         assert!(
-            std::process::Command::new("cargo")
-                .args(["rdme", "--check"])
-                .output()
-                .expect("couldn't run `cargo rdme`")
-                .status
-                .success(),
-            "README.md is out of date - bless the new version by running `cargo rdme`"
-        )
+            !expanded.contains(&quote!(Yak::Unit {}).to_string()),
+            "{}",
+            expanded
+        );
     }
 
     #[test]
-    fn attr_args() {
-        assert_eq!(
-            AttrArgs {
-                skip: true,
-                ..Default::default()
-            },
-            parse_quote!(skip),
-        );
-        assert_eq!(
-            AttrArgs {
-                default: true,
-                ..Default::default()
-            },
-            parse_quote!(default),
-        );
-        assert_eq!(
-            AttrArgs {
-                gen: Some(NameArgs {
-                    name_span: Span::call_site(),
-                    args: quote!(some_fn)
-                }),
-                ..Default::default()
-            },
-            parse_quote!(gen(some_fn)),
-        );
-        assert_eq!(
-            AttrArgs {
-                r#where: Some(NameArgs {
-                    name_span: Span::call_site(),
-                    args: quote!(foo)
-                }),
-                ..Default::default()
-            },
-            parse_quote!(where(foo)),
-        );
+    fn struct_constructors_keep_delimiters() {
+        for (input, ctor) in [
+            (
+                quote!(
+                    struct Yak;
+                ),
+                quote!(let value: Self = { Yak };),
+            ),
+            (
+                quote!(
+                    struct Yak();
+                ),
+                quote!(let value: Self = { Yak() };),
+            ),
+            (
+                quote!(
+                    struct Yak(u8, #[arbitrary(default)] u8);
+                ),
+                quote!(Yak(
+                    ::quickcheck::Arbitrary::arbitrary(g),
+                    ::core::default::Default::default()
+                )),
+            ),
+            (
+                quote!(
+                    struct Yak {}
+                ),
+                quote!(let value: Self = { Yak {} };),
+            ),
+        ] {
+            let expanded = expand_arbitrary(syn::parse2(input).unwrap())
+                .unwrap()
+                .to_string();
+            assert!(expanded.contains(&ctor.to_string()), "{}", expanded);
+        }
     }
 
     #[test]
     fn trybuild() {
         let t = trybuild::TestCases::new();
         t.pass("trybuild/pass/**/*.rs");
-        t.compile_fail("trybuild/fail/**/*.rs")
+        t.compile_fail("trybuild/fail/**/*.rs");
+        // `#[arbitrary(proptest)]` is only an error without the feature
+        #[cfg(feature = "proptest")]
+        t.compile_fail("trybuild/proptest/**/*.rs");
+        #[cfg(not(feature = "proptest"))]
+        t.compile_fail("trybuild/no_proptest/**/*.rs");
     }
 
     impl PartialEq for AttrArgs {
         fn eq(&self, other: &Self) -> bool {
-            fn norm(t: &AttrArgs) -> (Option<String>, &bool, &bool, Option<String>) {
+            /// (arguments, flags)
+            fn norm(t: &AttrArgs) -> (Vec<Option<String>>, Vec<bool>) {
                 let AttrArgs {
                     gen,
+                    gen_iter,
                     skip,
                     default,
+                    len,
+                    byte_len,
+                    range,
                     r#where,
+                    bound_for,
+                    include_deprecated,
+                    distinct,
+                    finite,
+                    finite_floats,
+                    system_time,
+                    duration,
+                    ipv4,
+                    ipv6,
+                    char,
+                    try_gen,
+                    validate,
+                    head,
+                    tail_len,
+                    repeat_with,
+                    each,
+                    seed_field,
+                    exclude_if,
+                    constructor,
+                    flatten,
+                    no_shrink_target,
+                    respect_serde_skip,
+                    portable,
+                    round_robin,
+                    unbiased,
+                    no_clone,
+                    distinct_variants,
+                    type_shrink,
+                    instrument,
+                    rare,
+                    shrink_in_place,
+                    with_gen,
+                    proptest,
+                    error_if_generic,
                 } = t;
                 (
-                    gen.as_ref().map(|it| it.args.to_string()),
-                    skip,
-                    default,
-                    r#where.as_ref().map(|it| it.args.to_string()),
+                    vec![
+                        gen.as_ref().map(|it| it.args.to_string()),
+                        gen_iter.as_ref().map(|it| it.args.to_string()),
+                        len.as_ref().map(|it| it.args.to_string()),
+                        byte_len.as_ref().map(|it| it.args.to_string()),
+                        range.as_ref().map(|it| it.args.to_string()),
+                        r#where.as_ref().map(|it| it.args.to_string()),
+                        bound_for.as_ref().map(|it| it.args.to_string()),
+                        validate.as_ref().map(|it| it.args.to_string()),
+                        head.as_ref().map(|it| it.args.gen.args.to_string()),
+                        tail_len.as_ref().map(|it| it.args.to_string()),
+                        repeat_with.as_ref().map(|it| it.args.gen.args.to_string()),
+                        repeat_with.as_ref().map(|it| it.args.len.args.to_string()),
+                        each.as_ref().map(|it| it.args.to_string()),
+                        seed_field.as_ref().map(|it| it.value.to_string()),
+                        exclude_if.as_ref().map(|it| it.value.to_string()),
+                        constructor.as_ref().map(|it| it.value.to_string()),
+                        with_gen.as_ref().map(|it| it.args.to_string()),
+                        char.as_ref().map(|it| {
+                            format!(
+                                "{} {} {}",
+                                it.args.ascii,
+                                it.args.alphanumeric,
+                                it.args
+                                    .ranges
+                                    .as_ref()
+                                    .map(|it| it.value.to_string())
+                                    .unwrap_or_default()
+                            )
+                        }),
+                        try_gen.as_ref().map(|it| {
+                            format!(
+                                "{} or {}",
+                                it.args.gen,
+                                it.args
+                                    .or
+                                    .as_ref()
+                                    .map(ToString::to_string)
+                                    .unwrap_or_default()
+                            )
+                        }),
+                        duration.as_ref().map(|it| {
+                            it.args
+                                .as_ref()
+                                .and_then(|it| it.secs.as_ref())
+                                .map(|it| it.value.to_string())
+                                .unwrap_or_default()
+                        }),
+                    ],
+                    vec![
+                        *skip,
+                        *default,
+                        *include_deprecated,
+                        *distinct,
+                        *finite,
+                        *finite_floats,
+                        *system_time,
+                        *ipv4,
+                        *ipv6,
+                        *flatten,
+                        *no_shrink_target,
+                        *respect_serde_skip,
+                        *portable,
+                        *round_robin,
+                        *unbiased,
+                        *no_clone,
+                        *distinct_variants,
+                        *type_shrink,
+                        *instrument,
+                        *rare,
+                        *shrink_in_place,
+                        *proptest,
+                        *error_if_generic,
+                    ],
                 )
             }
             norm(self) == norm(other)
@@ -0,0 +1,340 @@
+//! A [`proptest::arbitrary::Arbitrary`](https://docs.rs/proptest/latest/proptest/arbitrary/trait.Arbitrary.html)
+//! impl alongside the [`quickcheck::Arbitrary`] one, for items with `#[arbitrary(proptest)]`.
+//!
+//! Everything is drawn from proptest strategies, so values replay from proptest's seed,
+//! and the variant and every member shrink.
+//! The variant is picked with a weighted [`Union`](https://docs.rs/proptest/latest/proptest/strategy/struct.Union.html),
+//! and free members with [`any`](https://docs.rs/proptest/latest/proptest/arbitrary/fn.any.html).
+//! Arguments that need a [`quickcheck::Gen`] have no equivalent, so are rejected.
+
+use crate::{
+    collection_item, dedup_predicates, get_args, hashed_collection, interior_mutable_inner,
+    is_recursive_option, is_serde_skipped, is_type_named, marker_value, nested_tuple,
+    owned_pointee, Alternative, Arg, Value, COMMON_WEIGHT,
+};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use syn::{
+    parse_quote, punctuated::Punctuated, token::Comma, Expr, Generics, Path, Type, WhereClause,
+    WherePredicate,
+};
+
+/// [`quickcheck::Gen`]'s default size, which caps open-ended `len`s.
+const GEN_SIZE: usize = 100;
+
+/// Arguments that the `proptest` impl can't honour.
+pub(crate) const UNSUPPORTED: &[&str] = &[
+    "gen",
+    "gen_iter",
+    "gen_fn",
+    "try_gen",
+    "byte_len",
+    "system_time",
+    "duration",
+    "ipv4",
+    "ipv6",
+    "char",
+    "head",
+    "tail_len",
+    "repeat_with",
+    "each",
+    "distinct",
+    "validate",
+    "seed_field",
+];
+
+/// Reject the first of `arg`'s arguments that's [`UNSUPPORTED`].
+pub(crate) fn check_supported(arg: &Arg) -> syn::Result<()> {
+    match arg
+        .names
+        .iter()
+        .find(|it| UNSUPPORTED.contains(&it.to_string().as_str()))
+    {
+        Some(name) => Err(syn::Error::new(
+            name.span(),
+            format!("`{}` isn't supported with `proptest`", name),
+        )),
+        None => Ok(()),
+    }
+}
+
+pub(crate) struct Item<'a> {
+    pub(crate) name: &'a Ident,
+    pub(crate) generics: &'a Generics,
+    /// The item's own, and from its `#[arbitrary(where(...))]`.
+    pub(crate) predicates: Punctuated<WherePredicate, Comma>,
+    pub(crate) allow_deprecated: bool,
+    pub(crate) respect_serde_skip: bool,
+    /// Builds the struct from its members, in declaration order.
+    pub(crate) constructor: Option<Path>,
+    pub(crate) is_enum: bool,
+}
+
+pub(crate) fn expand(
+    Item {
+        name,
+        generics,
+        mut predicates,
+        allow_deprecated,
+        respect_serde_skip,
+        constructor,
+        is_enum,
+    }: Item,
+    alternatives: Vec<Alternative>,
+) -> syn::Result<TokenStream> {
+    // required by the trait, and for `BoxedStrategy`
+    predicates.push(parse_quote!(Self: ::core::fmt::Debug + 'static));
+    // shrinking only moves to earlier options, so never into a `no_shrink_target` variant
+    let (targets, others) = alternatives
+        .into_iter()
+        .partition::<Vec<_>, _>(|it| it.shrink_target);
+    let allow_deprecated = allow_deprecated.then(|| quote!(#[allow(deprecated)]));
+    let mut options = vec![];
+    for Alternative {
+        path,
+        fields,
+        rare,
+        exclude_if,
+        shrink_target: _,
+    } in targets.into_iter().chain(others)
+    {
+        let bindings = (0..fields.len())
+            .map(|ix| Ident::new(&format!("f{}", ix), Span::call_site()))
+            .collect::<Vec<_>>();
+        let mut strategies = vec![];
+        for (field, member, _) in &fields {
+            let arg = get_args(&field.attrs)?;
+            check_supported(&arg)?;
+            predicates.extend(arg.r#where.clone().into_iter().flatten());
+            let label = format!(
+                "{}.{}",
+                quote!(#path).to_string().replace(" :: ", "::"),
+                quote!(#member),
+            );
+            strategies.push(field_strategy(
+                field,
+                arg,
+                name,
+                respect_serde_skip,
+                &label,
+                &mut predicates,
+            )?);
+        }
+        let value = match &constructor {
+            Some(constructor) => quote!(#constructor(#(#bindings),*)),
+            None => {
+                let members = fields.iter().map(|(_, member, _)| member);
+                quote!(#path { #(#members: #bindings),* })
+            }
+        };
+        let strategy = match fields.is_empty() {
+            true => quote!(::proptest::strategy::LazyJust::new(|| #value)),
+            false => {
+                let strategies = nested_tuple(&strategies);
+                let pattern = nested_tuple(&bindings);
+                quote!(::proptest::strategy::Strategy::prop_map(
+                    #strategies,
+                    |#pattern| #value,
+                ))
+            }
+        };
+        let weight = match rare {
+            true => 1,
+            false => COMMON_WEIGHT as u32,
+        };
+        let push = quote! {
+            options.push((#weight, ::proptest::strategy::Strategy::boxed(#strategy)));
+        };
+        options.push(match exclude_if {
+            Some(path) => quote! {
+                if !#path() {
+                    #push
+                }
+            },
+            None => push,
+        });
+    }
+    let exhausted = match is_enum {
+        true => format!("every variant of `{}` is excluded", name),
+        false => format!("`{}` can't be generated", name),
+    };
+    let where_clause = WhereClause {
+        where_token: Default::default(),
+        // each `T` member adds `T: Arbitrary`
+        predicates: dedup_predicates(predicates),
+    };
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::proptest::arbitrary::Arbitrary for #name #ty_generics
+            #where_clause
+        {
+            type Parameters = ();
+            type Strategy = ::proptest::strategy::BoxedStrategy<Self>;
+            #allow_deprecated
+            fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+                let mut options = ::std::vec::Vec::<(
+                    ::core::primitive::u32,
+                    ::proptest::strategy::BoxedStrategy<Self>,
+                )>::new();
+                #(#options)*
+                ::core::assert!(!options.is_empty(), "{}", #exhausted);
+                ::proptest::strategy::Strategy::boxed(
+                    ::proptest::strategy::Union::new_weighted(options),
+                )
+            }
+        }
+    })
+}
+
+/// A strategy for one member, adding any bounds it needs to `predicates`.
+fn field_strategy(
+    field: &syn::Field,
+    Arg {
+        value,
+        finite,
+        flatten,
+        ..
+    }: Arg,
+    item: &Ident,
+    respect_serde_skip: bool,
+    label: &str,
+    predicates: &mut Punctuated<WherePredicate, Comma>,
+) -> syn::Result<TokenStream> {
+    let ty = &field.ty;
+    // as the `quickcheck` impl would
+    let value = match value {
+        None if respect_serde_skip && !finite && !flatten && is_serde_skipped(&field.attrs) => {
+            Some(Value::Default)
+        }
+        value => value,
+    };
+    // collected either way
+    if let (Some((_, _, hasher)), None | Some(Value::Len(_))) = (hashed_collection(ty), &value) {
+        predicates.push(parse_quote! {
+            #hasher: ::std::hash::BuildHasher + ::core::default::Default
+        });
+    }
+    Ok(match value {
+        Some(Value::Default) => quote! {
+            ::proptest::strategy::LazyJust::new(<#ty as ::core::default::Default>::default)
+        },
+        Some(Value::DefaultExpr(expr)) => quote! {
+            ::proptest::strategy::LazyJust::new(|| -> #ty { #expr })
+        },
+        Some(Value::Len(range)) => len_strategy(ty, &range, label, predicates),
+        Some(Value::Range(range)) => range_strategy(ty, &range, label),
+        Some(_) => unreachable!("the rest are `UNSUPPORTED`"),
+        None if marker_value(ty).is_some() => {
+            let marker = marker_value(ty);
+            quote!(::proptest::strategy::LazyJust::new(|| #marker))
+        }
+        None if is_recursive_option(ty, item) => {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "recursive members aren't supported with `proptest`; see `prop_recursive`",
+            ))
+        }
+        None if owned_pointee(ty).is_some() => {
+            let owned = owned_pointee(ty);
+            predicates.push(parse_quote!(#owned: ::proptest::arbitrary::Arbitrary));
+            quote! {
+                ::proptest::strategy::Strategy::prop_map(
+                    ::proptest::arbitrary::any::<#owned>(),
+                    ::core::convert::Into::<#ty>::into,
+                )
+            }
+        }
+        None if interior_mutable_inner(ty).is_some() => {
+            let inner = interior_mutable_inner(ty);
+            predicates.push(parse_quote!(#inner: ::proptest::arbitrary::Arbitrary));
+            quote! {
+                ::proptest::strategy::Strategy::prop_map(
+                    ::proptest::arbitrary::any::<#inner>(),
+                    <#ty>::new,
+                )
+            }
+        }
+        // proptest only has impls for the default hasher
+        None if hashed_collection(ty).is_some() => {
+            let (key, value, _) = hashed_collection(ty).unwrap();
+            predicates.push(parse_quote! {
+                #key: ::proptest::arbitrary::Arbitrary + ::core::cmp::Eq + ::core::hash::Hash
+            });
+            let item = match value {
+                Some(value) => {
+                    predicates.push(parse_quote!(#value: ::proptest::arbitrary::Arbitrary));
+                    quote!((#key, #value))
+                }
+                None => quote!(#key),
+            };
+            quote! {
+                ::proptest::strategy::Strategy::prop_map(
+                    ::proptest::collection::vec(::proptest::arbitrary::any::<#item>(), 0..=#GEN_SIZE),
+                    |items| ::core::iter::Iterator::collect::<#ty>(::core::iter::IntoIterator::into_iter(items)),
+                )
+            }
+        }
+        // proptest's floats are already finite
+        None => {
+            predicates.push(parse_quote!(#ty: ::proptest::arbitrary::Arbitrary));
+            quote!(::proptest::arbitrary::any::<#ty>())
+        }
+    })
+}
+
+/// Collect a `len` in `range` of arbitrary elements into `ty`.
+///
+/// Open-ended ranges are capped at [`GEN_SIZE`], as with a default [`quickcheck::Gen`].
+fn len_strategy(
+    ty: &Type,
+    range: &Expr,
+    label: &str,
+    predicates: &mut Punctuated<WherePredicate, Comma>,
+) -> TokenStream {
+    // `String` collects several item types, so `len` counts `char`s
+    let item = match is_type_named(ty, &["String"]) {
+        true => quote!(::core::primitive::char),
+        false => collection_item(ty),
+    };
+    predicates.push(parse_quote!(#item: ::proptest::arbitrary::Arbitrary));
+    let empty = format!("`len` range is empty for `{}`", label);
+    quote! {{
+        let range = #range;
+        let start = match ::core::ops::RangeBounds::<::core::primitive::usize>::start_bound(&range) {
+            ::core::ops::Bound::Included(it) => *it,
+            ::core::ops::Bound::Excluded(it) => *it + 1,
+            ::core::ops::Bound::Unbounded => 0,
+        };
+        let end = match ::core::ops::RangeBounds::<::core::primitive::usize>::end_bound(&range) {
+            ::core::ops::Bound::Included(it) => *it,
+            ::core::ops::Bound::Excluded(it) => it.checked_sub(1).expect(#empty),
+            ::core::ops::Bound::Unbounded => ::core::cmp::max(start, #GEN_SIZE),
+        };
+        ::core::assert!(start <= end, "{}", #empty);
+        ::proptest::strategy::Strategy::prop_map(
+            ::proptest::collection::vec(::proptest::arbitrary::any::<#item>(), start..=end),
+            |items| ::core::iter::Iterator::collect::<#ty>(::core::iter::IntoIterator::into_iter(items)),
+        )
+    }}
+}
+
+/// An integer `ty` in `range`.
+fn range_strategy(ty: &Type, range: &Expr, label: &str) -> TokenStream {
+    let empty = format!("`range` is empty for `{}`", label);
+    quote! {{
+        let range = #range;
+        let start = match ::core::ops::RangeBounds::<#ty>::start_bound(&range) {
+            ::core::ops::Bound::Included(it) => *it,
+            ::core::ops::Bound::Excluded(it) => it.checked_add(1).expect(#empty),
+            ::core::ops::Bound::Unbounded => <#ty>::MIN,
+        };
+        let end = match ::core::ops::RangeBounds::<#ty>::end_bound(&range) {
+            ::core::ops::Bound::Included(it) => *it,
+            ::core::ops::Bound::Excluded(it) => it.checked_sub(1).expect(#empty),
+            ::core::ops::Bound::Unbounded => <#ty>::MAX,
+        };
+        ::core::assert!(start <= end, "{}", #empty);
+        start..=end
+    }}
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "proptest")]
+
+use derive_quickcheck_arbitrary::Arbitrary;
+use proptest::{prelude::*, strategy::ValueTree as _, test_runner::TestRunner};
+
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+#[arbitrary(proptest)]
+struct Yak {
+    name: String,
+    #[arbitrary(default)]
+    always_zero: u8,
+    #[arbitrary(default = 7)]
+    always_seven: u8,
+    #[arbitrary(len(1..=3))]
+    herd: Vec<u8>,
+    #[arbitrary(range(10..20))]
+    age: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+#[arbitrary(proptest)]
+enum Shaver {
+    #[arbitrary(no_shrink_target)]
+    No(bool),
+    Yes {
+        speed: u8,
+    },
+    #[arbitrary(skip)]
+    _Skipped,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(proptest, where(T: quickcheck::Arbitrary))]
+struct GenericYak<T> {
+    name: T,
+    #[arbitrary(len = 2)]
+    nicknames: Vec<T>,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(proptest)]
+struct Unit;
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(proptest)]
+struct Wide(u8, u8, u8, u8, u8, u8, u8, u8, u8, #[arbitrary(default)] u8);
+
+#[derive(Debug, Clone, Default)]
+struct FxBuildHasher;
+
+impl std::hash::BuildHasher for FxBuildHasher {
+    type Hasher = std::collections::hash_map::DefaultHasher;
+    fn build_hasher(&self) -> Self::Hasher {
+        Default::default()
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(proptest)]
+struct Registry<S> {
+    _by_id: std::collections::HashMap<u8, String, S>,
+    #[arbitrary(len(..=4))]
+    tags: std::collections::HashSet<u8, S>,
+}
+
+/// Without `#[arbitrary(proptest)]`, so this impl doesn't conflict.
+#[derive(Debug, Clone, Arbitrary)]
+struct QuickcheckOnly;
+
+impl proptest::arbitrary::Arbitrary for QuickcheckOnly {
+    type Parameters = ();
+    type Strategy = Just<Self>;
+    fn arbitrary_with((): ()) -> Self::Strategy {
+        Just(QuickcheckOnly)
+    }
+}
+
+proptest! {
+    #[test]
+    fn honours_attributes(yak: Yak) {
+        prop_assert_eq!(yak.always_zero, 0);
+        prop_assert_eq!(yak.always_seven, 7);
+        prop_assert!((1..=3).contains(&yak.herd.len()));
+        prop_assert!((10..20).contains(&yak.age));
+    }
+
+    #[test]
+    fn skips_variants(shaver: Shaver) {
+        prop_assert_ne!(shaver, Shaver::_Skipped);
+    }
+
+    #[test]
+    fn can_generate_generic(yak: GenericYak<String>) {
+        prop_assert_eq!(yak.nicknames.len(), 2);
+    }
+
+    #[test]
+    fn can_generate_unit_and_wide(_unit: Unit, wide: Wide) {
+        prop_assert_eq!(wide.9, 0);
+    }
+
+    #[test]
+    fn can_generate_collections_with_custom_hasher(registry: Registry<FxBuildHasher>) {
+        prop_assert!(registry.tags.len() <= 4);
+    }
+}
+
+#[test]
+fn shrinking_holds_defaults_and_bounds() {
+    let mut runner = TestRunner::deterministic();
+    let mut tree = any::<Yak>().new_tree(&mut runner).unwrap();
+    while tree.simplify() {
+        let yak = tree.current();
+        assert_eq!(yak.always_zero, 0);
+        assert_eq!(yak.always_seven, 7);
+        assert!((1..=3).contains(&yak.herd.len()));
+        assert!((10..20).contains(&yak.age));
+    }
+}
+
+#[test]
+fn replays_from_the_seed() {
+    let generate = || {
+        let mut runner = TestRunner::deterministic();
+        (0..10)
+            .map(|_| any::<Yak>().new_tree(&mut runner).unwrap().current())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(generate(), generate());
+}
+
+#[test]
+fn shrinks_the_variant_and_its_members() {
+    let mut runner = TestRunner::deterministic();
+    for _ in 0..100 {
+        let mut tree = any::<Shaver>().new_tree(&mut runner).unwrap();
+        if let Shaver::No(_) = tree.current() {
+            continue;
+        }
+        while tree.simplify() {}
+        assert_eq!(tree.current(), Shaver::Yes { speed: 0 });
+    }
+}
@@ -0,0 +1,249 @@
+//! Derives in a module without the prelude, where its names mean something else,
+//! so generated code can only use fully qualified paths.
+
+#[no_implicit_prelude]
+#[allow(dead_code, non_camel_case_types)]
+mod shadowed {
+    use ::derive_quickcheck_arbitrary::Arbitrary;
+    use ::quickcheck::Gen;
+
+    pub struct Vec;
+    pub struct String;
+    pub struct Box;
+    pub struct Option;
+    pub struct Result;
+    pub struct Some;
+    pub struct None;
+    pub struct Ok;
+    pub struct Err;
+    pub struct Cell;
+    pub struct usize;
+    pub struct u32;
+    pub struct u64;
+    pub struct bool;
+    pub struct char;
+    pub trait Default {}
+    pub trait Clone {}
+    pub trait Iterator {}
+    pub trait IntoIterator {}
+    pub trait FromIterator {}
+    pub trait Extend {}
+    pub trait From {}
+    pub trait Into {}
+    pub trait Fn {}
+    pub trait FnOnce {}
+    pub trait Sized {}
+
+    // generated code must reach for `::core`'s
+    #[allow(unused_macros)]
+    macro_rules! assert {
+        ($($tt:tt)*) => {
+            ::core::compile_error!("the prelude's `assert!` is shadowed")
+        };
+    }
+    #[allow(unused_macros)]
+    macro_rules! unreachable {
+        ($($tt:tt)*) => {
+            ::core::compile_error!("the prelude's `unreachable!` is shadowed")
+        };
+    }
+    #[allow(unused_macros)]
+    macro_rules! panic {
+        ($($tt:tt)*) => {
+            ::core::compile_error!("the prelude's `panic!` is shadowed")
+        };
+    }
+
+    pub fn small(g: &mut Gen) -> ::core::primitive::u8 {
+        <::core::primitive::u8 as ::quickcheck::Arbitrary>::arbitrary(g) % 10
+    }
+
+    pub fn never() -> ::core::primitive::bool {
+        false
+    }
+
+    pub struct Below(pub ::core::primitive::u8);
+
+    impl Below {
+        pub fn generate(&self, g: &mut Gen) -> ::core::primitive::u8 {
+            small(g) % self.0
+        }
+        pub fn shrink(
+            &self,
+            it: &::core::primitive::u8,
+        ) -> ::std::boxed::Box<dyn ::core::iter::Iterator<Item = ::core::primitive::u8>> {
+            ::std::boxed::Box::new(::core::iter::Iterator::rev(0..*it))
+        }
+    }
+
+    pub struct Wrapped<'a>(pub &'a mut Gen);
+
+    impl<'a> ::core::convert::From<&'a mut Gen> for Wrapped<'a> {
+        fn from(g: &'a mut Gen) -> Self {
+            Self(g)
+        }
+    }
+
+    #[derive(::core::fmt::Debug, ::core::clone::Clone, Arbitrary)]
+    #[arbitrary(seed_field = seed, shrink_in_place)]
+    #[arbitrary(validate(|it: &Self| it.order_first <= it.order_second + 100))]
+    pub struct Members {
+        pub seed: ::core::primitive::u8,
+        pub free: ::core::primitive::u8,
+        #[arbitrary(gen(|g, built| small(g) + built.seed % 2))]
+        pub with_built: ::core::primitive::u8,
+        #[arbitrary(gen = small)]
+        pub named: ::core::primitive::u8,
+        #[arbitrary(gen_iter(|g| ::core::iter::Iterator::map(0..3, move |_| small(g))))]
+        pub collected: ::std::vec::Vec<::core::primitive::u8>,
+        #[arbitrary(try_gen(|g| <::core::primitive::u8 as ::core::convert::TryFrom<_>>::try_from(small(g) as ::core::primitive::u16)))]
+        pub tried: ::core::primitive::u8,
+        #[arbitrary(default)]
+        pub defaulted: ::std::string::String,
+        #[arbitrary(default = 3)]
+        pub default_expr: ::core::primitive::u8,
+        #[arbitrary(len(1..=3))]
+        pub len: ::std::vec::Vec<::core::primitive::u8>,
+        #[arbitrary(len = 2)]
+        pub chars: ::std::string::String,
+        #[arbitrary(byte_len(2..=4))]
+        pub bytes: ::std::string::String,
+        #[arbitrary(range(1..=9))]
+        pub range: ::core::primitive::u64,
+        #[arbitrary(each(range(0..3)))]
+        pub each: [::core::primitive::u8; 3],
+        #[arbitrary(char(ranges = ['a'..='f']))]
+        pub char: ::core::primitive::char,
+        #[arbitrary(system_time)]
+        pub system_time: ::std::time::SystemTime,
+        #[arbitrary(duration(secs = 0..=5))]
+        pub duration: ::core::time::Duration,
+        #[arbitrary(ipv4)]
+        pub ip: ::std::net::IpAddr,
+        #[arbitrary(head(gen(|_| 0)), tail_len(0..=2))]
+        pub head: ::std::vec::Vec<::core::primitive::u8>,
+        #[arbitrary(repeat_with(gen(small), len(1..=2)))]
+        pub repeat_with: ::std::collections::VecDeque<::core::primitive::u8>,
+        #[arbitrary(distinct, len(2..=3))]
+        pub distinct: ::std::vec::Vec<::core::primitive::u8>,
+        #[arbitrary(finite)]
+        pub finite: ::core::primitive::f64,
+        #[arbitrary(gen_fn = Below(5))]
+        pub strategy: ::core::primitive::u8,
+        #[arbitrary(gen(small), type_shrink)]
+        pub type_shrink: ::core::primitive::u8,
+        #[arbitrary(order = 1, gen(small))]
+        pub order_first: ::core::primitive::u8,
+        #[arbitrary(order = 2, gen(small))]
+        pub order_second: ::core::primitive::u8,
+        pub cell: ::core::cell::Cell<::core::primitive::u8>,
+        pub boxed: ::std::boxed::Box<::core::primitive::str>,
+        pub boxed_array: ::std::boxed::Box<[::core::primitive::u8; 4]>,
+        pub marker: ::core::marker::PhantomPinned,
+        pub next: ::core::option::Option<::std::boxed::Box<Self>>,
+    }
+
+    #[derive(::core::fmt::Debug, ::core::clone::Clone, Arbitrary)]
+    #[arbitrary(size = 5)]
+    pub struct Outer {
+        #[arbitrary(flatten)]
+        pub inner: Inner,
+        pub tuple: (::core::primitive::u8, ::core::primitive::u16),
+    }
+
+    #[derive(::core::fmt::Debug, ::core::clone::Clone, Arbitrary)]
+    #[arbitrary(constructor = Inner::new)]
+    pub struct Inner(::core::primitive::u8);
+
+    impl Inner {
+        fn new(it: ::core::primitive::u8) -> Self {
+            Self(it)
+        }
+    }
+
+    #[derive(::core::fmt::Debug, ::core::clone::Clone, Arbitrary)]
+    #[arbitrary(with_gen(Wrapped<'_>))]
+    pub struct WithGen {
+        #[arbitrary(gen(|wrapped| small(wrapped.0)))]
+        pub wrapped: ::core::primitive::u8,
+        #[arbitrary(gen_iter(|wrapped| ::core::iter::Iterator::map(0..2, move |_| small(wrapped.0))))]
+        pub collected: ::std::vec::Vec<::core::primitive::u8>,
+    }
+
+    #[derive(::core::fmt::Debug, ::core::clone::Clone, Arbitrary)]
+    #[arbitrary(instrument, distinct_variants)]
+    pub enum Plain {
+        Unit,
+        Tuple(::core::primitive::u8),
+        Struct {
+            name: ::std::string::String,
+        },
+        #[arbitrary(rare)]
+        Rare(::core::primitive::u16),
+        #[arbitrary(exclude_if = never)]
+        Excludable,
+        #[arbitrary(skip)]
+        Skipped(::core::primitive::u8),
+        #[deprecated]
+        Deprecated,
+    }
+
+    #[derive(::core::fmt::Debug, ::core::clone::Clone, Arbitrary)]
+    #[arbitrary(round_robin)]
+    pub enum RoundRobin {
+        First(::core::primitive::u8),
+        Second,
+    }
+
+    #[derive(::core::fmt::Debug, ::core::clone::Clone, Arbitrary)]
+    #[arbitrary(unbiased)]
+    pub enum Unbiased {
+        First,
+        #[arbitrary(rare)]
+        Second,
+    }
+
+    #[derive(::core::fmt::Debug, ::core::clone::Clone, Arbitrary)]
+    #[arbitrary(portable, no_clone)]
+    pub enum Portable {
+        First(::core::primitive::u8),
+        #[arbitrary(rare)]
+        Second(::core::primitive::u8),
+    }
+
+    #[derive(::core::fmt::Debug, ::core::clone::Clone, Arbitrary)]
+    #[arbitrary(unbiased)]
+    pub enum Excluding {
+        First(::core::primitive::u8),
+        #[arbitrary(exclude_if = never)]
+        #[arbitrary(rare)]
+        Second(::core::primitive::u8),
+    }
+}
+
+use quickcheck::{Arbitrary as _, Gen};
+use shadowed::{Excluding, Members, Outer, Plain, Portable, RoundRobin, Unbiased, WithGen};
+
+#[test]
+fn derives_without_the_prelude() {
+    let mut g = Gen::new(10);
+    for _ in 0..100 {
+        let members = Members::arbitrary(&mut g);
+        assert_eq!(members.collected.len(), 3);
+        assert!(members.shrink().next().is_some() || members.free == 0);
+        Outer::arbitrary(&mut g).shrink().for_each(drop);
+        assert_eq!(WithGen::arbitrary(&mut g).collected.len(), 2);
+        Plain::arbitrary(&mut g).shrink().for_each(drop);
+        RoundRobin::arbitrary(&mut g).shrink().for_each(drop);
+        Unbiased::arbitrary(&mut g).shrink().for_each(drop);
+        Portable::arbitrary(&mut g).shrink().for_each(drop);
+        Excluding::arbitrary(&mut g).shrink().for_each(drop);
+    }
+    assert_eq!(
+        Plain::arbitrary_variant_counts()
+            .iter()
+            .map(|(_, n)| n)
+            .sum::<u64>(),
+        100
+    );
+}
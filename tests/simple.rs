@@ -1,5 +1,6 @@
-use derive_quickcheck_arbitrary::Arbitrary;
-use quickcheck::quickcheck;
+use derive_quickcheck_arbitrary::{arbitrary_fields, arbitrary_for, arbitrary_impl, Arbitrary};
+use pasture::{Fence, Gate, Grazing, Paddock};
+use quickcheck::{quickcheck, Arbitrary as _};
 
 #[derive(Debug, Clone, Arbitrary)]
 struct Yak {
@@ -28,11 +29,1966 @@ enum Shaver {
     Empty(#[arbitrary(gen(|_|String::new()))] String),
 }
 
+#[derive(Debug, Clone, Arbitrary)]
+enum Clippers {
+    Electric,
+    #[deprecated]
+    _Manual,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(include_deprecated)]
+enum AllClippers {
+    Electric,
+    #[deprecated]
+    Manual,
+}
+
 #[derive(Debug, Clone, Arbitrary)]
 #[arbitrary(where(T: Default + Clone + 'static))]
 struct GenericYak<T> {
     #[arbitrary(default)]
-    inner: T,
+    inner: T,
+}
+
+mod barn {
+    use derive_quickcheck_arbitrary::Arbitrary;
+
+    /// Public type, private generator - nothing private should leak out of the impl.
+    #[derive(Debug, Clone, Arbitrary)]
+    pub struct Stall {
+        #[arbitrary(gen(private_number))]
+        pub number: u8,
+        #[arbitrary(len(..=2))]
+        pub occupants: Vec<String>,
+    }
+
+    fn private_number(g: &mut quickcheck::Gen) -> u8 {
+        *g.choose(&[1, 2, 3]).unwrap()
+    }
+}
+
+type Names = Vec<String>;
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Herd {
+    #[arbitrary(len(1..=3))]
+    names: Names,
+    #[arbitrary(len(..2))]
+    ids: Vec<usize>,
+}
+
+#[test]
+#[allow(deprecated)]
+fn can_include_deprecated_variants() {
+    let mut g = quickcheck::Gen::new(10);
+    assert!(std::iter::repeat_with(|| AllClippers::arbitrary(&mut g))
+        .take(1000)
+        .any(|it| matches!(it, AllClippers::Manual)));
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+enum Direction {
+    North,
+    South,
+    East,
+    West,
+    #[arbitrary(skip)]
+    _Up,
+}
+
+#[test]
+fn fieldless_enums_generate_every_variant() {
+    let mut g = quickcheck::Gen::new(10);
+    let generated = std::iter::repeat_with(|| Direction::arbitrary(&mut g))
+        .take(1000)
+        .collect::<Vec<_>>();
+    for direction in [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+    ] {
+        assert!(generated.contains(&direction), "{:?}", direction);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Arbitrary)]
+#[repr(u8)]
+enum Status {
+    Ok = 0,
+    #[arbitrary(skip)]
+    _Reserved = 100,
+    Failed = 200,
+    Unknown = 255,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Arbitrary)]
+#[arbitrary(unbiased)]
+enum UnbiasedStatus {
+    Ok = 1,
+    #[arbitrary(skip)]
+    Reserved = 2,
+    Failed = 4,
+}
+
+#[test]
+fn skipping_leaves_discriminant_gaps() {
+    let mut g = quickcheck::Gen::new(10);
+    let generated = std::iter::repeat_with(|| Status::arbitrary(&mut g) as u8)
+        .take(1000)
+        .collect::<Vec<_>>();
+    assert!(!generated.contains(&100));
+    for status in [Status::Ok, Status::Failed, Status::Unknown] {
+        assert!(generated.contains(&(status as u8)), "{:?}", status);
+    }
+    let generated = std::iter::repeat_with(|| UnbiasedStatus::arbitrary(&mut g))
+        .take(1000)
+        .collect::<Vec<_>>();
+    assert!(!generated.contains(&UnbiasedStatus::Reserved));
+    assert!(generated.contains(&UnbiasedStatus::Ok));
+    assert!(generated.contains(&UnbiasedStatus::Failed));
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+enum Empties {
+    Unit,
+    Tuple(),
+    Struct {},
+    #[arbitrary(skip)]
+    _SkippedUnit,
+    #[arbitrary(skip)]
+    _SkippedTuple(),
+    #[arbitrary(skip)]
+    _SkippedStruct {},
+}
+
+#[test]
+fn generates_each_kind_of_empty_variant() {
+    let mut g = quickcheck::Gen::new(10);
+    let generated = std::iter::repeat_with(|| Empties::arbitrary(&mut g))
+        .take(1000)
+        .collect::<Vec<_>>();
+    assert!(generated.contains(&Empties::Unit));
+    assert!(generated.contains(&Empties::Tuple()));
+    assert!(generated.contains(&Empties::Struct {}));
+    assert!(generated
+        .iter()
+        .all(|it| matches!(it, Empties::Unit | Empties::Tuple() | Empties::Struct {})));
+}
+
+/// Variants that are never generated, but can still be built by hand.
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+enum Retired {
+    Working(u8),
+    #[arbitrary(skip)]
+    Resting(u8, String),
+    #[arbitrary(skip)]
+    Stored {
+        fleece: Vec<u8>,
+    },
+    #[deprecated]
+    Sold,
+}
+
+#[test]
+#[allow(deprecated)]
+fn skipped_variants_shrink_to_nothing() {
+    for retired in [
+        Retired::Resting(100, String::from("shorn")),
+        Retired::Stored {
+            fleece: vec![1, 2, 3],
+        },
+        Retired::Sold,
+    ] {
+        assert_eq!(retired.shrink().count(), 0, "{:?}", retired);
+    }
+    assert!(Retired::Working(100).shrink().next().is_some());
+    assert_eq!(Empties::_SkippedTuple().shrink().count(), 0);
+    assert_eq!(Wrapper::_Never.shrink().count(), 0);
+}
+
+// `cfg_attr` is expanded before the derive sees it, and merges with the rest
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+enum Gated {
+    Always,
+    #[cfg_attr(feature = "proptest", arbitrary(skip))]
+    #[arbitrary(no_shrink_target)]
+    WithoutProptest,
+    #[cfg_attr(all(), arbitrary(skip))]
+    #[arbitrary(skip)]
+    _Never,
+    #[cfg_attr(any(), arbitrary(skip))]
+    NotGated,
+}
+
+#[test]
+fn cfg_attr_can_skip_variants() {
+    let mut g = quickcheck::Gen::new(10);
+    let generated = std::iter::repeat_with(|| Gated::arbitrary(&mut g))
+        .take(1000)
+        .collect::<Vec<_>>();
+    assert!(generated.contains(&Gated::Always));
+    assert!(generated.contains(&Gated::NotGated));
+    assert!(!generated.contains(&Gated::_Never));
+    assert_eq!(
+        generated.contains(&Gated::WithoutProptest),
+        cfg!(not(feature = "proptest"))
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+#[arbitrary(round_robin)]
+enum Turn {
+    Left,
+    #[arbitrary(skip)]
+    _Back,
+    Right(bool),
+    Straight {
+        _speed: u8,
+    },
+}
+
+#[test]
+fn round_robin_cycles_through_variants() {
+    // a fresh counter
+    std::thread::spawn(|| {
+        let mut g = quickcheck::Gen::new(10);
+        let turns = std::iter::repeat_with(|| Turn::arbitrary(&mut g))
+            .take(6)
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            turns.as_slice(),
+            [
+                Turn::Left,
+                Turn::Right(_),
+                Turn::Straight { .. },
+                Turn::Left,
+                Turn::Right(_),
+                Turn::Straight { .. },
+            ]
+        ));
+    })
+    .join()
+    .unwrap();
+}
+
+trait Grazes {
+    fn appetite(&self) -> u8;
+}
+
+impl Grazes for u8 {
+    fn appetite(&self) -> u8 {
+        *self
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(where(T: quickcheck::Arbitrary))]
+struct Grazer<T: Grazes> {
+    animal: T,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct WhereGrazer<T>
+where
+    T: Grazes + quickcheck::Arbitrary,
+{
+    animal: T,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(bound_for(T = "quickcheck::Arbitrary + Grazes"))]
+struct Flock<T, U: quickcheck::Arbitrary> {
+    leader: T,
+    followers: Vec<U>,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Pair<A, B>(
+    #[arbitrary(where(A: quickcheck::Arbitrary))] A,
+    #[arbitrary(len(1..=3), where(B: quickcheck::Arbitrary))] Vec<B>,
+);
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Pen<T> {
+    #[arbitrary(gen(|g| vec![T::arbitrary(g)]), where(T: quickcheck::Arbitrary))]
+    yaks: Vec<T>,
+}
+
+/// A `FxHashMap`-style hasher.
+#[derive(Debug, Clone, Default)]
+struct FxBuildHasher;
+
+impl std::hash::BuildHasher for FxBuildHasher {
+    type Hasher = std::collections::hash_map::DefaultHasher;
+    fn build_hasher(&self) -> Self::Hasher {
+        Default::default()
+    }
+}
+
+/// The hasher's bounds are inferred.
+#[derive(Debug, Clone, Arbitrary)]
+struct Registry<S> {
+    _by_id: std::collections::HashMap<u8, String, S>,
+    #[arbitrary(len(..=4))]
+    tags: std::collections::HashSet<u8, S>,
+}
+
+/// Only [`PartialEq`], so can't go in a `HashSet`.
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+struct Fleece(u8);
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Shearing {
+    #[arbitrary(len(..=8), distinct)]
+    fleeces: Vec<Fleece>,
+    #[arbitrary(distinct)]
+    ids: Vec<u16>,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(finite_floats)]
+struct Measurements {
+    weight: f64,
+    height: f32,
+    _horns: u8,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Speed {
+    #[arbitrary(finite)]
+    kph: f64,
+    _anything: f64,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Shave {
+    #[arbitrary(system_time)]
+    started: std::time::SystemTime,
+    #[arbitrary(gen(|_| std::time::Instant::now()))]
+    _timer: std::time::Instant,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Timings {
+    #[arbitrary(duration(secs = 60..=3600))]
+    took: std::time::Duration,
+    #[arbitrary(duration(secs = ..10))]
+    short: core::time::Duration,
+    #[arbitrary(duration)]
+    since_last: std::time::Duration,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Endpoints {
+    v4: std::net::Ipv4Addr,
+    v6: std::net::Ipv6Addr,
+    any: std::net::IpAddr,
+    socket: std::net::SocketAddr,
+    #[arbitrary(ipv4)]
+    forced_v4: std::net::IpAddr,
+    #[arbitrary(ipv6)]
+    forced_v6: std::net::IpAddr,
+    #[arbitrary(ipv6)]
+    socket_v6: std::net::SocketAddr,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Glyphs {
+    #[arbitrary(char(ascii))]
+    ascii: char,
+    #[arbitrary(char(alphanumeric))]
+    alphanumeric: char,
+    #[arbitrary(each(char(alphanumeric)))]
+    tag: [char; 4],
+    // either side of the surrogates
+    #[arbitrary(char(ranges = ['\u{d7ff}'..='\u{e000}', 'x'..='x']))]
+    edges: char,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Port(#[arbitrary(range(1..=65535))] u16);
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Labels {
+    #[arbitrary(len(3..=5))]
+    chars: String,
+    #[arbitrary(len = 4, distinct)]
+    distinct_chars: String,
+    #[arbitrary(byte_len(..=8))]
+    bytes: String,
+    #[arbitrary(byte_len(7..=7))]
+    exact_bytes: String,
+}
+
+/// Collections with their own `Arbitrary` impls, which `len` collects into.
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(where(T: quickcheck::Arbitrary + Ord))]
+struct Queues<T> {
+    heap: std::collections::BinaryHeap<T>,
+    deque: std::collections::VecDeque<T>,
+    #[arbitrary(len(1..=3))]
+    short_heap: std::collections::BinaryHeap<T>,
+    #[arbitrary(len = 2)]
+    pair: std::collections::VecDeque<String>,
+}
+
+/// Iterators collected into their members, borrowing the `Gen` or not.
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(where(T: quickcheck::Arbitrary + Ord))]
+struct Collected<T> {
+    #[arbitrary(gen_iter(|g| (0..5).map(|_| u8::arbitrary(g) % 10)))]
+    digits: Vec<u8>,
+    #[arbitrary(gen_iter(|_| ['y', 'a', 'k']))]
+    name: String,
+    #[arbitrary(gen_iter(|g| std::iter::repeat_with(|| T::arbitrary(g)).take(3)))]
+    sorted: std::collections::BTreeSet<T>,
+    #[arbitrary(gen_iter(|g| (1..=3).map(|id| (id, bool::arbitrary(g)))))]
+    shorn: std::collections::HashMap<u32, bool>,
+    #[arbitrary(each(gen_iter(|_| 0..2)))]
+    pairs: [Vec<u8>; 2],
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Readings {
+    #[arbitrary(range(-40..50))]
+    temperature: i8,
+    #[arbitrary(range(i64::MIN..=i64::MAX))]
+    anything: i64,
+    #[arbitrary(range(u64::MAX - 1..))]
+    huge: u64,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Pin {
+    #[arbitrary(each(range(0..=9)))]
+    digits: [u8; 4],
+    #[arbitrary(each(len(1..=2)))]
+    words: [Vec<u8>; 3],
+    #[arbitrary(each(gen(|_| 'x')))]
+    marks: [char; 2],
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+enum Bounded {
+    Small(#[arbitrary(range(..3))] usize),
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Csv {
+    #[arbitrary(head(gen(|_| String::from("name,age"))), tail_len(0..=4))]
+    lines: Vec<String>,
+    #[arbitrary(head(gen(|_| 0)))]
+    checksums: Vec<u8>,
+}
+
+// helper attributes are inert, so derive order doesn't matter
+#[derive(Arbitrary, Clone, Debug, serde::Serialize)]
+struct ArbitraryFirst {
+    #[arbitrary(gen(|_| 1))]
+    #[serde(rename = "the_answer")]
+    answer: u8,
+}
+
+#[derive(Clone, Debug, serde::Serialize, Arbitrary)]
+struct ArbitraryLast {
+    #[serde(rename = "the_answer")]
+    #[arbitrary(gen(|_| 1))]
+    answer: u8,
+}
+
+#[derive(Debug, Clone, Arbitrary, serde::Serialize)]
+#[arbitrary(respect_serde_skip)]
+struct SerdeSkipped {
+    #[serde(rename = "the_name")]
+    name: String,
+    #[serde(skip)]
+    cache: Vec<u8>,
+    #[serde(skip_serializing_if = "Option::is_none", skip)]
+    also_cached: Option<u8>,
+    #[serde(skip)]
+    #[arbitrary(gen(|_| 1))]
+    overridden: u8,
+    #[serde(skip_serializing)]
+    generated: Vec<u8>,
+}
+
+/// For `gen_fn`.
+mod strategies {
+    use quickcheck::{Arbitrary as _, Gen};
+
+    pub struct InRange {
+        pub start: u8,
+        pub end: u8,
+    }
+
+    impl InRange {
+        pub fn generate(&self, g: &mut Gen) -> u8 {
+            self.start + u8::arbitrary(g) % (self.end - self.start)
+        }
+        pub fn shrink(&self, it: &u8) -> Box<dyn Iterator<Item = u8>> {
+            Box::new((self.start..*it).rev())
+        }
+    }
+
+    pub const PERCENT: InRange = InRange { start: 0, end: 101 };
+
+    pub struct LenOf(pub usize);
+
+    impl LenOf {
+        pub fn generate(&self, g: &mut Gen) -> Vec<u8> {
+            std::iter::repeat_with(|| u8::arbitrary(g))
+                .take(self.0)
+                .collect()
+        }
+        pub fn shrink(&self, it: &[u8]) -> impl Iterator<Item = Vec<u8>> {
+            let len = self.0;
+            it.to_vec().shrink().filter(move |it| it.len() == len)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+struct Strategic {
+    #[arbitrary(gen_fn = strategies::PERCENT)]
+    percent: u8,
+    #[arbitrary(gen_fn = strategies::LenOf(3))]
+    triple: Vec<u8>,
+    free: u8,
+}
+
+#[test]
+fn gen_fn_shrinks_with_its_strategy() {
+    let strategic = Strategic {
+        percent: 2,
+        triple: vec![0, 0, 1],
+        free: 1,
+    };
+    let shrunk = strategic.shrink().collect::<Vec<_>>();
+    assert_eq!(
+        shrunk,
+        [
+            Strategic {
+                free: 0,
+                ..strategic.clone()
+            },
+            Strategic {
+                percent: 1,
+                ..strategic.clone()
+            },
+            Strategic {
+                percent: 0,
+                ..strategic.clone()
+            },
+            Strategic {
+                triple: vec![0, 0, 0],
+                ..strategic.clone()
+            },
+        ]
+    );
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(seed_field = seed)]
+struct Fallible {
+    seed: u8,
+    #[arbitrary(try_gen(|g| u8::try_from(u16::arbitrary(g) % 512)))]
+    sometimes: u8,
+    #[arbitrary(try_gen(|_| Err::<Vec<u8>, _>("never")))]
+    defaulted: Vec<u8>,
+    #[arbitrary(try_gen(|_| "not a number".parse(), or = Self::FALLBACK))]
+    fallback: u32,
+    #[arbitrary(try_gen(|_, built| Ok::<_, ()>(built.seed)))]
+    seeded: u8,
+}
+
+impl Fallible {
+    const FALLBACK: u32 = 7;
+}
+
+const KEY_LEN: usize = 4;
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Exact {
+    #[arbitrary(len = 16)]
+    id: Vec<u8>,
+    #[arbitrary(len = KEY_LEN, distinct)]
+    key: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Repeated {
+    #[arbitrary(repeat_with(gen(|g| u8::arbitrary(g) % 10), len(1..=10)))]
+    ids: Vec<u8>,
+    #[arbitrary(repeat_with(gen(|_| 7), len(1..=3)))]
+    unique: std::collections::HashSet<u32>,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(where(T: quickcheck::Arbitrary + From<u8>))]
+struct Tagged<T> {
+    #[arbitrary(gen(|g| (u8::arbitrary(g) % 10).into()))]
+    tag: T,
+    #[arbitrary(gen(|g| vec![T::arbitrary(g)]))]
+    others: Vec<T>,
+}
+
+#[derive(Debug, Clone)]
+struct NoDefault(u8);
+
+impl From<u8> for NoDefault {
+    fn from(it: u8) -> Self {
+        Self(it)
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(where(T: From<u8> + Clone + 'static))]
+struct Defaulted<T> {
+    #[arbitrary(default = T::from(7))]
+    seven: T,
+    #[arbitrary(default = vec![1, 2])]
+    pair: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Versioned {
+    #[arbitrary(default = Self::VERSION)]
+    version: u32,
+    _payload: Vec<u8>,
+}
+
+impl Versioned {
+    const VERSION: u32 = 3;
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(portable)]
+enum Portable {
+    North,
+    South(u8),
+    East { _bearing: u16 },
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(unbiased)]
+enum Unbiased {
+    North,
+    South(u8),
+    East { _bearing: u16 },
+}
+
+#[test]
+fn unbiased_picks_variants_uniformly() {
+    const DRAWS: usize = 30_000;
+    let mut g = quickcheck::Gen::new(10);
+    let mut counts = [0; 3];
+    for _ in 0..DRAWS {
+        counts[match Unbiased::arbitrary(&mut g) {
+            Unbiased::North => 0,
+            Unbiased::South(_) => 1,
+            Unbiased::East { .. } => 2,
+        }] += 1;
+    }
+    let expected = DRAWS as f64 / 3.0;
+    let chi_square = counts
+        .iter()
+        .map(|it| (*it as f64 - expected).powi(2) / expected)
+        .sum::<f64>();
+    // p < 0.0001 for 2 degrees of freedom
+    assert!(chi_square < 18.4, "{:?}", counts);
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+enum Counterexample {
+    Simple(u8),
+    #[arbitrary(no_shrink_target)]
+    Awkward(u8),
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Chosen {
+    #[arbitrary(gen(|rng| *rng.choose(&[1, 2, 3]).unwrap()))]
+    small: u8,
+    #[arbitrary(gen(|rng| rng.size()))]
+    size: usize,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(where(T: quickcheck::Arbitrary))]
+struct Cells<T> {
+    count: std::cell::Cell<u8>,
+    shared: std::cell::RefCell<Vec<T>>,
+}
+
+#[derive(Debug, Arbitrary)]
+struct Locked {
+    name: std::sync::Mutex<String>,
+    names: std::sync::RwLock<Vec<String>>,
+    count: u8,
+}
+
+impl Clone for Locked {
+    fn clone(&self) -> Self {
+        Self {
+            name: std::sync::Mutex::new(self.name.lock().unwrap().clone()),
+            names: std::sync::RwLock::new(self.names.read().unwrap().clone()),
+            count: self.count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Pinned {
+    _pin: std::marker::PhantomPinned,
+    _unit: (),
+    _name: String,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(where(T: quickcheck::Arbitrary))]
+struct Unsized<T> {
+    name: Box<str>,
+    shared_name: std::rc::Rc<str>,
+    bytes: std::sync::Arc<[u8]>,
+    items: Box<[T]>,
+    #[arbitrary(len(2..=2))]
+    pair: Box<[u8]>,
+}
+
+/// `T: Arbitrary` comes from the boxed array.
+#[derive(Debug, Clone, Arbitrary)]
+struct Keys<T, const N: usize> {
+    secret: Box<[u8; 32]>,
+    others: Box<[T; N]>,
+}
+
+trait Shaves: std::fmt::Debug {
+    fn speed(&self) -> u8;
+    fn box_clone(&self) -> Box<dyn Shaves + Send + Sync>;
+}
+
+#[derive(Debug, Clone)]
+struct Razor(u8);
+
+impl Shaves for Razor {
+    fn speed(&self) -> u8 {
+        self.0
+    }
+    fn box_clone(&self) -> Box<dyn Shaves + Send + Sync> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Shaves + Send + Sync> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Barber {
+    #[arbitrary(gen(|g| Box::new(Razor(u8::arbitrary(g) % 10))))]
+    razor: Box<dyn Shaves + Send + Sync>,
+    #[arbitrary(gen(|_| std::sync::Arc::new(Razor(1))))]
+    shared: std::sync::Arc<dyn Shaves + Send + Sync + 'static>,
+}
+
+fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+fn digits<const N: usize>(g: &mut quickcheck::Gen) -> [u8; N] {
+    std::array::from_fn(|_| u8::arbitrary(g) % 10)
+}
+
+/// Arrays sized by an associated const.
+#[derive(Debug, Clone, Arbitrary)]
+struct Fixed {
+    #[arbitrary(each(range(0..3)))]
+    each: [u8; Self::LEN],
+    #[arbitrary(gen(|g| digits::<{ Self::LEN }>(g)))]
+    data: [u8; Self::LEN],
+    #[arbitrary(gen(digits))]
+    named: [u8; Fixed::LEN],
+}
+
+impl Fixed {
+    const LEN: usize = 4;
+}
+
+/// Generators and arguments using the impl's const parameter.
+#[derive(Debug, Clone, Arbitrary)]
+struct Buf<const N: usize> {
+    #[arbitrary(gen(|g| digits::<N>(g)))]
+    data: [u8; N],
+    #[arbitrary(gen(digits::<N>))]
+    direct: [u8; N],
+    #[arbitrary(gen(|_| N))]
+    capacity: usize,
+    #[arbitrary(len(N..=N))]
+    spare: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Node {
+    value: u8,
+    next: Option<Box<Node>>,
+}
+
+impl Node {
+    fn len(&self) -> usize {
+        1 + self.next.as_ref().map_or(0, |it| it.len())
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+enum Tree {
+    Leaf(u8),
+    Branch(Option<Box<Self>>, Option<Box<Tree>>),
+}
+
+impl Tree {
+    fn depth(&self) -> usize {
+        match self {
+            Tree::Leaf(_) => 1,
+            Tree::Branch(left, right) => {
+                1 + [left, right]
+                    .iter()
+                    .filter_map(|it| it.as_ref())
+                    .map(|it| it.depth())
+                    .max()
+                    .unwrap_or(0)
+            }
+        }
+    }
+}
+
+#[test]
+fn recursive_options_terminate() {
+    let mut g = quickcheck::Gen::new(100);
+    let lens = std::iter::repeat_with(|| Node::arbitrary(&mut g).len())
+        .take(1000)
+        .collect::<Vec<_>>();
+    // sizes 100, 50, 25, 12, 6, 3 and 1 may nest
+    assert!(lens.iter().all(|it| *it <= 8), "{:?}", lens);
+    assert!(lens.iter().any(|it| *it > 1));
+    assert!(std::iter::repeat_with(|| Tree::arbitrary(&mut g))
+        .take(1000)
+        .all(|it| it.depth() <= 8));
+}
+
+std::thread_local! {
+    static FRAGILE: std::cell::Cell<bool> = std::cell::Cell::new(false);
+}
+
+fn fragile_value() -> u8 {
+    assert!(!FRAGILE.with(std::cell::Cell::get), "generation failed");
+    0
+}
+
+/// Generates `next` first, so a panic in `_value` is usually nested.
+#[derive(Debug, Clone, Arbitrary)]
+struct Fragile {
+    next: Option<Box<Self>>,
+    #[arbitrary(gen(|_| fragile_value()))]
+    _value: u8,
+}
+
+impl Fragile {
+    fn len(&self) -> usize {
+        1 + self.next.as_ref().map_or(0, |it| it.len())
+    }
+}
+
+#[test]
+fn recursion_depth_survives_panics() {
+    let mut g = quickcheck::Gen::new(100);
+    FRAGILE.with(|it| it.set(true));
+    for _ in 0..100 {
+        let generated =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| Fragile::arbitrary(&mut g)));
+        assert!(generated.is_err());
+    }
+    FRAGILE.with(|it| it.set(false));
+    // a stuck depth would stop any nesting
+    assert!(std::iter::repeat_with(|| Fragile::arbitrary(&mut g))
+        .take(100)
+        .any(|it| it.len() > 1));
+}
+
+/// `Arbitrary` requires `Clone`, but generation never calls it.
+#[derive(Debug, Arbitrary)]
+#[arbitrary(no_clone)]
+enum Uncloned {
+    Named { name: String },
+    Counted(u8, u8),
+    Empty,
+}
+
+impl Clone for Uncloned {
+    fn clone(&self) -> Self {
+        panic!("cloned while generating")
+    }
+}
+
+#[test]
+fn no_clone_builds_only_the_chosen_variant() {
+    let mut g = quickcheck::Gen::new(10);
+    let generated = std::iter::repeat_with(|| Uncloned::arbitrary(&mut g))
+        .take(1000)
+        .collect::<Vec<_>>();
+    assert!(generated
+        .iter()
+        .any(|it| matches!(it, Uncloned::Named { .. })));
+    assert!(generated
+        .iter()
+        .any(|it| matches!(it, Uncloned::Counted(..))));
+    assert!(generated.iter().any(|it| matches!(it, Uncloned::Empty)));
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(instrument)]
+enum Counted {
+    Shorn,
+    Grown(u8),
+    Wild {
+        horns: u8,
+    },
+    #[arbitrary(skip)]
+    _Never,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(instrument, round_robin)]
+enum Rotated {
+    First,
+    Second(u8),
+}
+
+#[test]
+fn instrument_counts_generated_variants() {
+    let mut g = quickcheck::Gen::new(10);
+    Counted::reset_arbitrary_variant_counts();
+    for _ in 0..3000 {
+        Counted::arbitrary(&mut g);
+    }
+    let counts = Counted::arbitrary_variant_counts();
+    assert_eq!(
+        counts.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+        ["Shorn", "Grown", "Wild"]
+    );
+    // chosen uniformly, so about 1000 each
+    assert!(
+        counts.iter().all(|(_, n)| (850..=1150).contains(n)),
+        "{:?}",
+        counts
+    );
+    assert_eq!(counts.iter().map(|(_, n)| n).sum::<u64>(), 3000);
+    Counted::reset_arbitrary_variant_counts();
+    assert!(Counted::arbitrary_variant_counts()
+        .iter()
+        .all(|(_, n)| *n == 0));
+
+    for _ in 0..10 {
+        Rotated::arbitrary(&mut g);
+    }
+    assert_eq!(
+        Rotated::arbitrary_variant_counts(),
+        [("First", 5), ("Second", 5)]
+    );
+}
+
+/// A catch-all, generated a tenth as often as each other variant.
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(instrument)]
+enum Frame {
+    Data(u8),
+    Ping,
+    Close {
+        code: u8,
+    },
+    #[arbitrary(rare)]
+    Unknown(u16),
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(instrument, unbiased)]
+enum UnbiasedFrame {
+    Data,
+    Ping,
+    Close,
+    #[arbitrary(rare)]
+    Unknown,
+}
+
+fn never() -> bool {
+    false
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(instrument)]
+enum ExcludableFrame {
+    Data(u8),
+    #[arbitrary(exclude_if = never)]
+    Ping,
+    Close(u8),
+    #[arbitrary(rare)]
+    Unknown(u16),
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(instrument, portable)]
+enum PortableFrame {
+    Data,
+    Ping,
+    Close,
+    #[arbitrary(rare)]
+    Unknown,
+}
+
+/// Generate `3100` values, and check every common variant came up
+/// about `1000` times and the `rare` one about `100`.
+macro_rules! assert_rare_last {
+    ($ty:ty) => {{
+        let mut g = quickcheck::Gen::new(10);
+        <$ty>::reset_arbitrary_variant_counts();
+        for _ in 0..3100 {
+            <$ty>::arbitrary(&mut g);
+        }
+        let counts = <$ty>::arbitrary_variant_counts();
+        let (rare, common) = counts.split_last().unwrap();
+        assert!((40..=200).contains(&rare.1), "{:?}", counts);
+        assert!(
+            common.iter().all(|(_, n)| (850..=1150).contains(n)),
+            "{:?}",
+            counts
+        );
+    }};
+}
+
+#[test]
+fn rare_variants_are_generated_infrequently() {
+    assert_rare_last!(Frame);
+    assert_rare_last!(UnbiasedFrame);
+    assert_rare_last!(ExcludableFrame);
+    assert_rare_last!(PortableFrame);
+}
+
+/// Unit variants only differ by name, and skipped ones are never chosen.
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(distinct_variants)]
+enum Moult {
+    Shorn {
+        wool: u8,
+    },
+    Grown {
+        wool: u16,
+    },
+    Tagged(u8),
+    Paired(u8, u8),
+    Wild,
+    Tame,
+    #[arbitrary(skip)]
+    _Copied {
+        wool: u8,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+struct Breed {
+    name: &'static str,
+}
+
+const NAMES: [&str; 2] = ["Jess", "Alex"];
+static BREEDS: [Breed; 2] = [Breed { name: "Wild" }, Breed { name: "Domestic" }];
+
+fn twice(g: &mut quickcheck::Gen) -> (u8, u8) {
+    (u8::arbitrary(g), u8::arbitrary(g))
+}
+
+fn pair_of<T: quickcheck::Arbitrary>(g: &mut quickcheck::Gen) -> Vec<T> {
+    vec![T::arbitrary(g), T::arbitrary(g)]
+}
+
+/// A generic generator return type, inside a struct-like variant.
+/// The generator needs its own bound, like any other generic member.
+#[derive(Debug, Clone, Arbitrary)]
+enum Nested<T> {
+    Wrapped {
+        #[arbitrary(gen(|g| pair_of(g)), where(T: quickcheck::Arbitrary))]
+        inner: Vec<T>,
+    },
+    Plain(u8),
+}
+
+/// Consecutive positional generators, each borrowing the `Gen` more than once.
+#[derive(Debug, Clone, Arbitrary)]
+struct Reborrowed(
+    #[arbitrary(gen(|g| (twice(g).0, twice(g).1)))] (u8, u8),
+    #[arbitrary(gen(|g| vec![u8::arbitrary(g), u8::arbitrary(g)]))] Vec<u8>,
+    #[arbitrary(gen(twice), order = 0)] (u8, u8),
+    #[arbitrary(try_gen(|g| u8::arbitrary(g).checked_add(u8::arbitrary(g)).ok_or(())))] u8,
+);
+
+#[derive(Debug, Clone, Arbitrary)]
+enum ReborrowedVariant {
+    Only(
+        #[arbitrary(gen(|g| (twice(g).0, twice(g).1)))] (u8, u8),
+        #[arbitrary(gen(|g| twice(g).0))] u8,
+    ),
+}
+
+const SHEAR: fn(&mut quickcheck::Gen) -> u8 = |g| u8::arbitrary(g) % 3;
+
+static CLIP: fn(&mut quickcheck::Gen) -> u8 = |g| u8::arbitrary(g) % 5 + 10;
+
+/// Generators kept in `const` and `static` fn pointers.
+#[derive(Debug, Clone, Arbitrary)]
+struct Pointed {
+    #[arbitrary(gen = SHEAR)]
+    sheared: u8,
+    #[arbitrary(gen = CLIP)]
+    clipped: u8,
+    #[arbitrary(gen(SHEAR))]
+    called: u8,
+    #[arbitrary(gen = |g| u8::arbitrary(g) % 2, order = 0)]
+    closure: u8,
+}
+
+/// A generator wrapper, made from the `Gen` for each call.
+struct Budget<'a> {
+    g: &'a mut quickcheck::Gen,
+}
+
+impl<'a> From<&'a mut quickcheck::Gen> for Budget<'a> {
+    fn from(g: &'a mut quickcheck::Gen) -> Self {
+        Self { g }
+    }
+}
+
+impl Budget<'_> {
+    fn spend(&mut self, max: u8) -> u8 {
+        u8::arbitrary(self.g) % max
+    }
+}
+
+/// Generators taking a wrapper instead of the `Gen`.
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(with_gen(Budget<'_>))]
+struct Budgeted {
+    #[arbitrary(gen(|budget| budget.spend(3)))]
+    small: u8,
+    #[arbitrary(try_gen(|budget| budget.spend(200).checked_add(budget.spend(100)).ok_or(()).map(|it| it % 7)))]
+    tried: u8,
+    #[arbitrary(each(gen(|budget| budget.spend(5))))]
+    each: [u8; 3],
+    #[arbitrary(gen_iter(|budget| (0..4).map(|_| budget.spend(6))))]
+    collected: Vec<u8>,
+    free: u8,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(with_gen(Budget<'_>))]
+enum BudgetedVariant {
+    Spent(#[arbitrary(gen(|budget| budget.spend(4)))] u8),
+}
+
+/// Generators picking from constant tables.
+#[derive(Debug, Clone, Arbitrary)]
+struct Tabled {
+    #[arbitrary(gen(|g| *g.choose(&NAMES).unwrap()))]
+    name: &'static str,
+    #[arbitrary(gen(|g| g.choose(&BREEDS).unwrap()))]
+    breed: &'static Breed,
+    #[arbitrary(gen(|g| g.choose(&NAMES[..0]).copied()))]
+    nothing: Option<&'static str>,
+}
+
+/// Generators returning types with explicit and elided `'static` lifetimes.
+#[derive(Debug, Arbitrary)]
+struct Lifetimes {
+    #[arbitrary(gen(|_| std::borrow::Cow::Borrowed("yak")))]
+    borrowed: std::borrow::Cow<'static, str>,
+    #[arbitrary(gen(|g| std::borrow::Cow::Owned(String::arbitrary(g))))]
+    owned: std::borrow::Cow<'static, str>,
+    #[arbitrary(gen(|_| Box::from("no yaks")))]
+    error: Box<dyn std::error::Error + Send + Sync + 'static>,
+    #[arbitrary(gen(|g| *g.choose(&["Jess", "Alex"]).unwrap()))]
+    name: &'static str,
+}
+
+impl Clone for Lifetimes {
+    fn clone(&self) -> Self {
+        Self {
+            borrowed: self.borrowed.clone(),
+            owned: self.owned.clone(),
+            error: Box::from(self.error.to_string()),
+            name: self.name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(seed_field = rng_seed)]
+struct Seeded {
+    #[arbitrary(gen(|_, built| built.rng_seed.wrapping_add(1)))]
+    next: u64,
+    rng_seed: u64,
+    #[arbitrary(head(gen(|_, built| built.rng_seed)), tail_len(1..=1))]
+    copies: Vec<u64>,
+    unrelated: u8,
+}
+
+fn tick() -> u32 {
+    std::thread_local! {
+        static TICKS: std::cell::Cell<u32> = std::cell::Cell::new(0);
+    }
+    TICKS.with(|it| {
+        it.set(it.get() + 1);
+        it.get()
+    })
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Ordered {
+    #[arbitrary(gen(|_| tick()))]
+    third: u32,
+    #[arbitrary(gen(|_| tick()), order = 1)]
+    second: u32,
+    #[arbitrary(gen(|_| tick()))]
+    fourth: u32,
+    #[arbitrary(order = 0)]
+    #[arbitrary(gen(|_| tick()))]
+    first: u32,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+enum OrderedVariant {
+    Only(
+        #[arbitrary(gen(|_| tick()))] u32,
+        #[arbitrary(gen(|_| tick()), order = 0)] u32,
+    ),
+}
+
+#[test]
+fn order_generates_later_fields_first() {
+    let Ordered {
+        first,
+        second,
+        third,
+        fourth,
+    } = Ordered::arbitrary(&mut quickcheck::Gen::new(10));
+    assert!(first < second && second < third && third < fourth);
+    let OrderedVariant::Only(later, earlier) =
+        OrderedVariant::arbitrary(&mut quickcheck::Gen::new(10));
+    assert!(earlier < later);
+}
+
+std::thread_local! {
+    static OFFLINE: std::cell::Cell<bool> = std::cell::Cell::new(false);
+}
+
+fn is_offline() -> bool {
+    OFFLINE.with(|it| it.get())
+}
+
+fn always() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+enum Fetch {
+    Cached,
+    #[arbitrary(exclude_if = is_offline)]
+    Download(u8),
+    #[arbitrary(exclude_if = is_offline)]
+    Stream,
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+#[arbitrary(round_robin)]
+enum FetchInTurn {
+    #[arbitrary(exclude_if = is_offline)]
+    Download,
+    Cached,
+    #[arbitrary(exclude_if = always)]
+    _Never,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+enum Unreachable {
+    #[arbitrary(exclude_if = always)]
+    _Excluded,
+}
+
+#[test]
+fn exclude_if_is_checked_on_each_call() {
+    let mut g = quickcheck::Gen::new(10);
+    let online = std::iter::repeat_with(|| Fetch::arbitrary(&mut g))
+        .take(1000)
+        .collect::<Vec<_>>();
+    assert!(online.contains(&Fetch::Cached));
+    assert!(online.contains(&Fetch::Stream));
+    assert!(online.iter().any(|it| matches!(it, Fetch::Download(_))));
+    assert_eq!(
+        [(); 4].map(|()| FetchInTurn::arbitrary(&mut g)),
+        [
+            FetchInTurn::Download,
+            FetchInTurn::Cached,
+            FetchInTurn::Download,
+            FetchInTurn::Cached
+        ]
+    );
+
+    OFFLINE.with(|it| it.set(true));
+    assert!(std::iter::repeat_with(|| Fetch::arbitrary(&mut g))
+        .take(1000)
+        .all(|it| it == Fetch::Cached));
+    assert!(std::iter::repeat_with(|| FetchInTurn::arbitrary(&mut g))
+        .take(10)
+        .all(|it| it == FetchInTurn::Cached));
+    OFFLINE.with(|it| it.set(false));
+}
+
+#[test]
+#[should_panic = "every variant of `Unreachable` is excluded"]
+fn exclude_if_can_exclude_everything() {
+    Unreachable::arbitrary(&mut quickcheck::Gen::new(10));
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Common {
+    #[arbitrary(len(3..=3))]
+    ids: Vec<u8>,
+    /// As long as the `Gen` is big.
+    #[arbitrary(gen(|g| vec![0; g.size()]))]
+    sized: Vec<u8>,
+}
+
+/// Its own size reaches the flattened `Common`.
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(size = 7)]
+struct Outer {
+    #[arbitrary(flatten)]
+    common: Common,
+    _extra: u8,
+}
+
+fn all_distinct<T: PartialEq>(items: &[T]) -> bool {
+    items
+        .iter()
+        .enumerate()
+        .all(|(ix, it)| !items[..ix].contains(it))
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Trimmer {
+    #[arbitrary(gen(|_| u8::wrapping_add))]
+    combine: fn(u8, u8) -> u8,
+}
+
+// `#[repr(...)]` shouldn't interact with generation at all
+
+#[derive(Debug, Clone, Arbitrary)]
+#[repr(transparent)]
+struct TransparentYak(String);
+
+#[derive(Debug, Clone, Arbitrary)]
+#[repr(C)]
+struct CYak {
+    _id: u32,
+    _name: String,
+}
+
+#[derive(Debug, Clone, Copy, Arbitrary)]
+#[repr(C, packed)]
+struct PackedYak {
+    _id: u32,
+    _horns: u8,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[repr(align(16))]
+struct AlignedYak(u8);
+
+#[derive(Debug, Clone, Copy, Arbitrary)]
+#[repr(u8)]
+enum Horn {
+    Left = 1,
+    Right = 2,
+    #[arbitrary(skip)]
+    _Broken = 3,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[repr(C, u8)]
+enum CShaver {
+    _Electric(u8),
+    _Manual { _blades: u16 },
+}
+
+#[test]
+fn repr_enums_only_generate_included_discriminants() {
+    let mut g = quickcheck::Gen::new(100);
+    let horns = std::iter::repeat_with(|| Horn::arbitrary(&mut g))
+        .take(1000)
+        .map(|it| it as u8)
+        .collect::<Vec<_>>();
+    assert!(!horns.contains(&3), "{:?}", horns);
+    assert!(horns.contains(&1) && horns.contains(&2), "{:?}", horns);
+    assert!(Horn::Left.shrink().all(|it| it as u8 != 3));
+}
+
+/// Its fields can't be borrowed to shrink them, so it's held as is.
+#[test]
+fn packed_structs_are_held_while_shrinking() {
+    let mut g = quickcheck::Gen::new(100);
+    for _ in 0..100 {
+        assert_eq!(PackedYak::arbitrary(&mut g).shrink().count(), 0);
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HandWritten {
+    name: String,
+    _id: usize,
+    herd: Vec<u8>,
+    always_one: u8,
+}
+
+impl quickcheck::Arbitrary for HandWritten {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        arbitrary_fields!(
+            Self {
+                #[arbitrary(gen(|_| String::from("yak")))]
+                name,
+                _id,
+                #[arbitrary(len(2..=2))]
+                herd,
+                always_one: 1,
+            },
+            g
+        )
+    }
+}
+
+mod pasture {
+    /// Can't derive here.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Grazing<T> {
+        pub yaks: Vec<T>,
+        pub acres: u8,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Fence(pub u8, pub bool);
+
+    /// Can't annotate here either.
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Paddock<T> {
+        pub name: String,
+        pub yaks: Vec<T>,
+        pub gates: u8,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Gate(pub u8, pub bool);
+}
+
+arbitrary_impl!(
+    #[arbitrary(where(T: quickcheck::Arbitrary))]
+    for Grazing<T> {
+        #[arbitrary(len(1..=2))]
+        yaks: Vec<T>,
+        acres: u8,
+    }
+);
+
+arbitrary_impl!(for Fence(#[arbitrary(default)] u8, bool););
+
+arbitrary_for!(
+    #[arbitrary(where(T: quickcheck::Arbitrary + PartialEq))]
+    Paddock<T>,
+    {
+        name: gen(|_| String::from("top field")),
+        yaks: (len(..=3), distinct),
+        gates,
+    }
+);
+
+arbitrary_for!(Gate, { 0: default = 1, 1 });
+
+#[test]
+fn spec_shrinks_like_derive() {
+    assert!(Gate(3, true).shrink().all(|it| it.0 == 3));
+    assert_eq!(Gate(3, true).shrink().count(), true.shrink().count());
+}
+
+#[test]
+fn impl_shrinks_like_derive() {
+    assert!(Fence(3, true).shrink().all(|it| it.0 == 3));
+    assert_eq!(Fence(3, true).shrink().count(), true.shrink().count());
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct OpenEnded {
+    #[arbitrary(len(1..))]
+    at_least_one: Vec<u8>,
+    #[arbitrary(len(8..))]
+    at_least_eight: Vec<u8>,
+}
+
+#[test]
+fn open_ended_len_is_capped_at_size() {
+    let mut g = quickcheck::Gen::new(5);
+    for _ in 0..1000 {
+        let OpenEnded {
+            at_least_one,
+            at_least_eight,
+        } = OpenEnded::arbitrary(&mut g);
+        assert!((1..=5).contains(&at_least_one.len()));
+        // the lower bound wins
+        assert_eq!(at_least_eight.len(), 8);
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(size = 5)]
+struct MediumSized {
+    #[arbitrary(len(1..))]
+    at_least_one: Vec<u8>,
+    herd: Vec<u8>,
+}
+
+#[test]
+fn item_size_overrides_the_callers() {
+    let mut g = quickcheck::Gen::new(1000);
+    for _ in 0..1000 {
+        let MediumSized { at_least_one, herd } = MediumSized::arbitrary(&mut g);
+        assert!((1..=5).contains(&at_least_one.len()));
+        assert!(herd.len() < 5);
+    }
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Impossible {
+    #[arbitrary(len(3..=3), distinct)]
+    _flags: Vec<bool>,
+}
+
+#[test]
+#[should_panic(
+    expected = "couldn't generate a distinct element for `Impossible._flags: Vec<bool>` after 100 attempts"
+)]
+fn distinct_panic_names_the_field() {
+    Impossible::arbitrary(&mut quickcheck::Gen::new(10));
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+enum EmptyLen {
+    Range {
+        #[arbitrary(len(3..3))]
+        _herd: Vec<u8>,
+    },
+}
+
+#[test]
+#[should_panic(expected = "`len` range is empty for `EmptyLen::Range._herd: Vec<u8>`")]
+fn len_panic_names_the_variant_field() {
+    EmptyLen::arbitrary(&mut quickcheck::Gen::new(10));
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(validate(|it: &Self| it.min <= it.max))]
+struct Validated {
+    #[arbitrary(gen(|g| u8::arbitrary(g) / 2))]
+    min: u8,
+    #[arbitrary(gen(|g| u8::arbitrary(g) / 2 + 128))]
+    max: u8,
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(validate(|it: &Self| it.min <= it.max))]
+struct BrokenValidated {
+    #[arbitrary(gen(|_| 2))]
+    min: u8,
+    #[arbitrary(gen(|_| 1))]
+    max: u8,
+}
+
+#[test]
+fn validate_passes_consistent_values() {
+    let validated = Validated::arbitrary(&mut quickcheck::Gen::new(10));
+    assert!(validated.min <= validated.max);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "generated value failed validation")]
+fn validate_catches_broken_generators() {
+    BrokenValidated::arbitrary(&mut quickcheck::Gen::new(10));
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+enum Wrapper {
+    Only(u8, #[arbitrary(default)] u16),
+    #[arbitrary(skip)]
+    _Never,
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+enum Wide {
+    Only {
+        a: u8,
+        b: u8,
+        c: u8,
+        d: u8,
+        e: u8,
+        f: u8,
+        g: u8,
+        #[arbitrary(default)]
+        h: u8,
+        i: u8,
+        j: u8,
+    },
+}
+
+#[test]
+fn single_variant_enum_shrinks_like_its_fields() {
+    assert_eq!(
+        Wrapper::Only(10, 3).shrink().collect::<Vec<_>>(),
+        10u8.shrink()
+            .map(|it| Wrapper::Only(it, 3))
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(Wrapper::_Never.shrink().count(), 0);
+    let wide = Wide::Only {
+        a: 0,
+        b: 0,
+        c: 0,
+        d: 0,
+        e: 0,
+        f: 0,
+        g: 0,
+        h: 7,
+        i: 0,
+        j: 4,
+    };
+    assert!(wide
+        .shrink()
+        .all(|it| matches!(it, Wide::Only { h: 7, .. })));
+    assert_eq!(wide.shrink().count(), 4u8.shrink().count());
+}
+
+/// [`Wide`], shrinking each member from a copy of the rest rather than as a tuple.
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+#[arbitrary(shrink_in_place)]
+struct WideInPlace {
+    a: u8,
+    b: u8,
+    c: u8,
+    d: u8,
+    e: u8,
+    f: u8,
+    g: u8,
+    #[arbitrary(default)]
+    h: u8,
+    i: u8,
+    j: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+#[arbitrary(shrink_in_place)]
+enum Regrown {
+    Shorn,
+    Grown(u8, #[arbitrary(default)] u16, Vec<u8>),
+}
+
+#[test]
+fn shrink_in_place_shrinks_like_a_tuple() {
+    let in_place = WideInPlace {
+        a: 3,
+        b: 0,
+        c: 9,
+        d: 0,
+        e: 0,
+        f: 1,
+        g: 0,
+        h: 7,
+        i: 0,
+        j: 4,
+    };
+    let wide = Wide::Only {
+        a: 3,
+        b: 0,
+        c: 9,
+        d: 0,
+        e: 0,
+        f: 1,
+        g: 0,
+        h: 7,
+        i: 0,
+        j: 4,
+    };
+    assert_eq!(
+        in_place
+            .shrink()
+            .map(
+                |WideInPlace {
+                     a,
+                     b,
+                     c,
+                     d,
+                     e,
+                     f,
+                     g,
+                     h,
+                     i,
+                     j,
+                 }| [a, b, c, d, e, f, g, h, i, j]
+            )
+            .collect::<Vec<_>>(),
+        wide.shrink()
+            .map(
+                |Wide::Only {
+                     a,
+                     b,
+                     c,
+                     d,
+                     e,
+                     f,
+                     g,
+                     h,
+                     i,
+                     j,
+                 }| [a, b, c, d, e, f, g, h, i, j]
+            )
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(
+        Regrown::Grown(5, 3, vec![1, 2])
+            .shrink()
+            .collect::<Vec<_>>(),
+        5u8.shrink()
+            .map(|it| Regrown::Grown(it, 3, vec![1, 2]))
+            .chain(vec![1u8, 2].shrink().map(|it| Regrown::Grown(5, 3, it)))
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(Regrown::Shorn.shrink().count(), 0);
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+enum Mixed {
+    Pinned {
+        #[arbitrary(gen(|_| 42))]
+        answer: u8,
+        free: u8,
+    },
+    Tuple(#[arbitrary(default)] u8, u8),
+    Unit,
+}
+
+#[derive(Debug, Clone, PartialEq, Arbitrary)]
+struct PinnedStruct {
+    #[arbitrary(len(3..=3))]
+    three: Vec<u8>,
+    free: u8,
+}
+
+#[test]
+fn unsized_pointees_are_held_while_shrinking() {
+    let it = Unsized::<u8> {
+        name: "yak".into(),
+        shared_name: "yak".into(),
+        bytes: vec![1].into(),
+        items: vec![2].into(),
+        pair: vec![3, 4].into(),
+    };
+    assert_eq!(it.shrink().count(), 0);
+}
+
+#[test]
+fn boxed_arrays_are_held_while_shrinking() {
+    let it = Keys::<u8, 2> {
+        secret: Box::new([7; 32]),
+        others: Box::new([1, 2]),
+    };
+    assert_eq!(it.shrink().count(), 0);
+}
+
+mod shears {
+    pub trait Sharp {}
+
+    impl Sharp for u8 {}
+
+    /// Bounds on traits named relative to the item's module.
+    pub mod flock {
+        use derive_quickcheck_arbitrary::Arbitrary;
+
+        #[derive(Debug, Clone, Arbitrary)]
+        #[arbitrary(where(T: quickcheck::Arbitrary + crate::shears::Sharp))]
+        pub struct FromCrate<T>(pub T);
+
+        #[derive(Debug, Clone, Arbitrary)]
+        #[arbitrary(where(T: quickcheck::Arbitrary + super::Sharp))]
+        pub struct FromParent<T>(pub T);
+    }
+}
+
+#[deny(warnings)]
+mod strict {
+    use derive_quickcheck_arbitrary::Arbitrary;
+
+    #[derive(Debug, Clone, Arbitrary)]
+    #[allow(non_snake_case)]
+    pub struct Shouty {
+        pub YAK_NAME: String,
+        #[arbitrary(default)]
+        pub HerdSize: u8,
+    }
+
+    #[derive(Debug, Clone, Arbitrary)]
+    #[allow(non_snake_case, non_camel_case_types)]
+    pub enum shouty_enum {
+        lower_case { ShavedAt: u8 },
+        Other(#[arbitrary(gen(|_| 1))] u8, u8),
+    }
+}
+
+#[test]
+fn unusual_names_shrink() {
+    let shouty = strict::Shouty {
+        YAK_NAME: String::from("yak"),
+        HerdSize: 3,
+    };
+    assert!(shouty.shrink().all(|it| it.HerdSize == 3));
+    assert!(strict::shouty_enum::Other(1, 2)
+        .shrink()
+        .all(|it| matches!(it, strict::shouty_enum::Other(1, _))));
+    assert!(strict::shouty_enum::lower_case { ShavedAt: 2 }
+        .shrink()
+        .all(|it| matches!(it, strict::shouty_enum::lower_case { .. })));
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Clamped {
+    #[arbitrary(gen(|g| u8::arbitrary(g) % 10 + 10))]
+    held: u8,
+    #[arbitrary(gen(|g| u8::arbitrary(g) % 10 + 10), type_shrink)]
+    loose: u8,
+}
+
+#[test]
+fn generated_members_only_shrink_out_of_range_when_asked() {
+    let clamped = Clamped {
+        held: 15,
+        loose: 15,
+    };
+    assert!(clamped.shrink().all(|it| it.held == 15));
+    assert!(clamped.shrink().any(|it| it.loose < 10));
+}
+
+#[test]
+fn shrink_holds_pinned_fields() {
+    let pinned = Mixed::Pinned {
+        answer: 42,
+        free: 10,
+    };
+    assert_eq!(
+        pinned.shrink().collect::<Vec<_>>(),
+        10u8.shrink()
+            .map(|free| Mixed::Pinned { answer: 42, free })
+            .collect::<Vec<_>>(),
+    );
+    assert!(Mixed::Tuple(5, 10)
+        .shrink()
+        .all(|it| matches!(it, Mixed::Tuple(5, _))));
+    assert_eq!(Mixed::Unit.shrink().count(), 0);
+    let pinned = PinnedStruct {
+        three: vec![1, 2, 3],
+        free: 10,
+    };
+    assert_eq!(pinned.shrink().count(), 10u8.shrink().count());
+    assert!(pinned.shrink().all(|it| it.three == [1, 2, 3]));
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+struct Sack {
+    items: Vec<u8>,
+}
+
+std::thread_local! {
+    static LAST_HEAVY: std::cell::RefCell<Option<Sack>> = std::cell::RefCell::new(None);
+}
+
+fn weight(items: &[u8]) -> u32 {
+    items.iter().copied().map(u32::from).sum()
+}
+
+fn is_light(sack: Sack) -> bool {
+    let light = weight(&sack.items) <= 100;
+    if !light {
+        LAST_HEAVY.with(|it| *it.borrow_mut() = Some(sack));
+    }
+    light
+}
+
+#[test]
+fn quickcheck_minimizes_with_derived_shrink() {
+    let result = quickcheck::QuickCheck::new()
+        .tests(1000)
+        .quicktest(is_light as fn(Sack) -> bool);
+    assert!(result.is_err());
+    // quickcheck keeps shrinking until nothing fails, so the last failure is the minimum
+    let Sack { items } = LAST_HEAVY.with(|it| it.take()).unwrap();
+    assert_eq!(weight(&items), 101, "{:?}", items);
+    assert!(!items.contains(&0), "{:?}", items);
+}
+
+mod shearing {
+    use derive_quickcheck_arbitrary::Arbitrary;
+
+    /// Only ever built through [`Shearing::from_parts`], which keeps `start <= end`.
+    #[derive(Debug, Clone, Arbitrary)]
+    #[arbitrary(constructor = Shearing::from_parts)]
+    pub struct Shearing {
+        #[arbitrary(range(1..=12))]
+        start: u8,
+        end: u8,
+    }
+
+    impl Shearing {
+        pub fn from_parts(a: u8, b: u8) -> Self {
+            Self {
+                start: a.min(b),
+                end: a.max(b),
+            }
+        }
+
+        pub fn start(&self) -> u8 {
+            self.start
+        }
+
+        pub fn end(&self) -> u8 {
+            self.end
+        }
+    }
+}
+
+#[test]
+fn constructed_values_hold_while_shrinking() {
+    let shearing = shearing::Shearing::from_parts(3, 200);
+    assert_eq!(shearing.shrink().count(), 0);
+}
+
+#[derive(Debug, Clone, Arbitrary)]
+#[arbitrary(where(A: quickcheck::Arbitrary))]
+#[arbitrary(where(B: quickcheck::Arbitrary + PartialEq))]
+struct SplitBounds<A, B> {
+    _a: A,
+    #[arbitrary(len(..=2))]
+    #[arbitrary(distinct)]
+    b: Vec<B>,
 }
 
 quickcheck! {
@@ -44,7 +2000,318 @@ quickcheck! {
         assert!(yak.inner.is_empty());
     }
 
+    fn can_generate_public_type_with_private_helpers(stall: barn::Stall) -> bool {
+        (1..=3).contains(&stall.number) && stall.occupants.len() <= 2
+    }
+
+    fn can_generate_aliased_collection(herd: Herd) -> bool {
+        (1..=3).contains(&herd.names.len()) && herd.ids.len() < 2
+    }
+
+    fn bound_for_bounds_one_parameter(flock: Flock<u8, String>) -> bool {
+        let _ = (flock.leader, flock.followers);
+        true
+    }
+
+    fn positional_members_can_be_bounded(pair: Pair<u8, String>) -> bool {
+        let _ = pair.0;
+        (1..=3).contains(&pair.1.len())
+    }
+
+    fn keeps_the_items_own_bounds(grazer: Grazer<u8>, where_grazer: WhereGrazer<u8>) -> bool {
+        let _ = (grazer.animal.appetite(), where_grazer.animal.appetite());
+        true
+    }
+
+    fn can_combine_member_gen_and_where(pen: Pen<String>) -> bool {
+        pen.yaks.len() == 1
+    }
+
+    fn can_generate_collections_with_custom_hasher(registry: Registry<FxBuildHasher>) -> bool {
+        registry.tags.len() <= 4
+    }
+
+    fn can_generate_distinct_elements(shearing: Shearing) -> bool {
+        shearing.fleeces.len() <= 8 && all_distinct(&shearing.fleeces) && all_distinct(&shearing.ids)
+    }
+
+    fn can_generate_finite_floats(measurements: Measurements, speed: Speed) -> bool {
+        measurements.weight.is_finite() && measurements.height.is_finite() && speed.kph.is_finite()
+    }
+
+    fn can_generate_system_time(shave: Shave) -> bool {
+        shave
+            .started
+            .duration_since(std::time::UNIX_EPOCH)
+            .is_ok_and(|it| it.as_secs() <= u32::MAX.into())
+    }
+
+    fn can_generate_durations(timings: Timings) -> bool {
+        (60..=3600).contains(&timings.took.as_secs())
+            && timings.short.as_secs() < 10
+            && timings.since_last.as_secs() <= u32::MAX.into()
+            && timings.since_last.subsec_nanos() == 0
+    }
+
+    fn ip_versions_can_be_forced(endpoints: Endpoints) -> bool {
+        let _ = (endpoints.v4, endpoints.v6, endpoints.any, endpoints.socket);
+        endpoints.forced_v4.is_ipv4() && endpoints.forced_v6.is_ipv6() && endpoints.socket_v6.is_ipv6()
+    }
+
+    fn generic_variant_fields_can_use_gen(nested: Nested<u8>) -> bool {
+        match nested {
+            Nested::Wrapped { inner } => inner.len() == 2,
+            Nested::Plain(_) => true,
+        }
+    }
+
+    fn can_generate_distinct_variants(moult: Moult) -> bool {
+        !matches!(moult, Moult::_Copied { .. })
+    }
+
+    fn char_categories_are_kept(glyphs: Glyphs) -> bool {
+        glyphs.ascii.is_ascii()
+            && glyphs.alphanumeric.is_ascii_alphanumeric()
+            && glyphs.tag.iter().all(char::is_ascii_alphanumeric)
+            && ['\u{d7ff}', '\u{e000}', 'x'].contains(&glyphs.edges)
+    }
+
+    fn len_counts_chars_and_byte_len_counts_bytes(labels: Labels) -> bool {
+        let distinct = labels.distinct_chars.chars().collect::<Vec<_>>();
+        (3..=5).contains(&labels.chars.chars().count())
+            && distinct.len() == 4
+            && all_distinct(&distinct)
+            && labels.bytes.len() <= 8
+            && labels.exact_bytes.len() == 7
+    }
+
+    fn gen_can_name_fn_pointer_items(pointed: Pointed) -> bool {
+        pointed.sheared < 3
+            && (10..15).contains(&pointed.clipped)
+            && pointed.called < 3
+            && pointed.closure < 2
+    }
+
+    fn arrays_can_be_sized_by_an_associated_const(fixed: Fixed) -> bool {
+        fixed.each.iter().all(|it| *it < 3)
+            && fixed.data.iter().chain(&fixed.named).all(|it| *it < 10)
+    }
+
+    fn gen_iter_collects_into_the_member(collected: Collected<u8>) -> bool {
+        collected.digits.len() == 5
+            && collected.digits.iter().all(|it| *it < 10)
+            && collected.name == "yak"
+            && (1..=3).contains(&collected.sorted.len())
+            && collected.shorn.len() == 3
+            && collected.pairs == [vec![0, 1], vec![0, 1]]
+    }
+
+    fn generators_can_take_a_wrapper(budgeted: Budgeted, variant: BudgetedVariant) -> bool {
+        let _ = budgeted.free;
+        let BudgetedVariant::Spent(spent) = variant;
+        budgeted.small < 3
+            && budgeted.tried < 7
+            && budgeted.each.iter().all(|it| *it < 5)
+            && budgeted.collected.len() == 4
+            && budgeted.collected.iter().all(|it| *it < 6)
+            && spent < 4
+    }
+
+    fn can_generate_heaps_and_deques(queues: Queues<u8>) -> bool {
+        let _ = (queues.heap.peek(), queues.deque.front());
+        (1..=3).contains(&queues.short_heap.len()) && queues.pair.len() == 2
+    }
+
+    fn range_keeps_integers_within_bounds(port: Port, readings: Readings, bounded: Bounded) -> bool {
+        let Bounded::Small(small) = bounded;
+        let _ = readings.anything;
+        port.0 >= 1
+            && (-40..50).contains(&readings.temperature)
+            && readings.huge >= u64::MAX - 1
+            && small < 3
+    }
+
+    fn each_constrains_every_element(pin: Pin) -> bool {
+        pin.digits.iter().all(|it| *it <= 9)
+            && pin.words.iter().all(|it| (1..=2).contains(&it.len()))
+            && pin.marks == ['x', 'x']
+    }
+
+    fn can_generate_custom_head(csv: Csv) -> bool {
+        csv.lines[0] == "name,age" && (1..=5).contains(&csv.lines.len()) && csv.checksums[0] == 0
+    }
+
+    fn derive_order_doesnt_matter(first: ArbitraryFirst, last: ArbitraryLast) -> bool {
+        first.answer == 1 && last.answer == 1
+    }
+
+    fn respects_serde_skip(skipped: SerdeSkipped) -> bool {
+        let _ = (skipped.name, skipped.generated);
+        skipped.cache.is_empty() && skipped.also_cached.is_none() && skipped.overridden == 1
+    }
+
+    fn gen_fn_generates_with_its_strategy(strategic: Strategic) -> bool {
+        strategic.percent <= 100 && strategic.triple.len() == 3
+    }
+
+    fn try_gen_falls_back(fallible: Fallible) -> bool {
+        let _ = fallible.sometimes;
+        fallible.defaulted.is_empty()
+            && fallible.fallback == Fallible::FALLBACK
+            && fallible.seeded == fallible.seed
+    }
+
+    fn len_can_be_exact(exact: Exact) -> bool {
+        exact.id.len() == 16 && exact.key.len() == KEY_LEN
+    }
+
+    fn can_repeat_custom_elements(repeated: Repeated) -> bool {
+        (1..=10).contains(&repeated.ids.len())
+            && repeated.ids.iter().all(|it| *it < 10)
+            && repeated.unique.into_iter().collect::<Vec<_>>() == [7]
+    }
+
+    fn default_expr_needs_no_default(defaulted: Defaulted<NoDefault>) -> bool {
+        defaulted.seven.0 == 7 && defaulted.pair == [1, 2]
+    }
+
+    fn default_expr_can_use_associated_consts(versioned: Versioned) -> bool {
+        versioned.version == Versioned::VERSION
+    }
+
+    fn can_use_gen_on_generic_fields(tagged: Tagged<u64>) -> bool {
+        tagged.tag < 10 && tagged.others.len() == 1
+    }
+
+    fn can_generate_unsized_pointees(it: Unsized<String>) -> bool {
+        let Unsized { name, shared_name, bytes, items, pair } = it;
+        let _ = (name.len(), shared_name.len(), bytes.len(), items.len());
+        pair.len() == 2
+    }
+
+    fn can_generate_boxed_arrays(it: Keys<String, 3>) -> bool {
+        it.secret.len() == 32 && it.others.len() == 3
+    }
+
+    fn can_generate_bounded_trait_objects(barber: Barber) -> bool {
+        assert_send_sync(&barber.razor);
+        assert_send_sync(&barber.shared);
+        barber.razor.speed() < 10 && barber.shared.speed() == 1
+    }
+
+    fn gen_can_use_const_params(buf: Buf<4>) -> bool {
+        buf.data.iter().chain(&buf.direct).all(|it| *it < 10)
+            && buf.capacity == 4
+            && buf.spare.len() == 4
+    }
+
+    fn positional_generators_reborrow(reborrowed: Reborrowed, variant: ReborrowedVariant) -> bool {
+        let ReborrowedVariant::Only(_, _) = variant;
+        let _ = (reborrowed.0, reborrowed.2, reborrowed.3);
+        reborrowed.1.len() == 2
+    }
+
+    fn gen_can_pick_from_tables(tabled: Tabled) -> bool {
+        NAMES.contains(&tabled.name) && BREEDS.contains(tabled.breed) && tabled.nothing.is_none()
+    }
+
+    fn gen_can_return_static_lifetimes(lifetimes: Lifetimes) -> bool {
+        assert_send_sync(&lifetimes.error);
+        lifetimes.borrowed == "yak"
+            && lifetimes.error.to_string() == "no yaks"
+            && ["Jess", "Alex"].contains(&lifetimes.name)
+            && lifetimes.shrink().count() == 0
+    }
+
+    fn seed_field_drives_other_fields(seeded: Seeded) -> bool {
+        let Seeded { next, rng_seed, copies, unrelated: _ } = seeded;
+        next == rng_seed.wrapping_add(1) && copies[0] == rng_seed
+    }
+
+    fn flatten_uses_nested_impl(outer: Outer) -> bool {
+        outer.common.ids.len() == 3 && outer.common.sized.len() == 7
+    }
+
+    fn can_generate_portable_enums(_portable: Portable) -> bool {
+        true
+    }
+
+    fn shrink_never_targets_excluded_variant(counterexample: Counterexample) -> bool {
+        match counterexample {
+            Counterexample::Simple(_) => counterexample
+                .shrink()
+                .all(|it| matches!(it, Counterexample::Simple(_))),
+            Counterexample::Awkward(_) => true,
+        }
+    }
+
+    fn gen_can_use_gen_methods(chosen: Chosen) -> bool {
+        (1..=3).contains(&chosen.small) && chosen.size > 0
+    }
+
+    fn can_generate_interior_mutability(cells: Cells<u8>, locked: Locked) -> bool {
+        let _ = (cells.count.get(), cells.shared.borrow().len());
+        let _ = (locked.name.lock().unwrap().len(), locked.names.read().unwrap().len());
+        locked.shrink().all(|it| *it.name.lock().unwrap() == *locked.name.lock().unwrap())
+    }
+
+    fn can_generate_markers(_pinned: Pinned) -> bool {
+        true
+    }
+
+    fn can_generate_fn_pointer_with_gen(trimmer: Trimmer) -> bool {
+        (trimmer.combine)(255, 1) == 0
+    }
+
+    fn can_generate_repr_types(
+        _transparent: TransparentYak,
+        _c: CYak,
+        _packed: PackedYak,
+        aligned: AlignedYak,
+        horn: Horn,
+        _c_enum: CShaver
+    ) -> bool {
+        std::ptr::addr_of!(aligned) as usize % 16 == 0 && matches!(horn as u8, 1 | 2)
+    }
+
+    fn can_reuse_field_generation(hand_written: HandWritten) -> bool {
+        hand_written.name == "yak" && hand_written.herd.len() == 2 && hand_written.always_one == 1
+    }
+
+    fn can_implement_from_a_spec(paddock: Paddock<u8>, gate: Gate) -> bool {
+        let _ = paddock.gates;
+        paddock.name == "top field"
+            && paddock.yaks.len() <= 3
+            && all_distinct(&paddock.yaks)
+            && gate.0 == 1
+    }
+
+    fn can_implement_for_foreign_structs(grazing: Grazing<String>, fence: Fence) -> bool {
+        (1..=2).contains(&grazing.yaks.len()) && fence.0 == 0
+    }
+
+    fn can_generate_through_constructor(shearing: shearing::Shearing) -> bool {
+        shearing.start() <= shearing.end()
+    }
+
+    fn where_paths_resolve_from_the_item(
+        _from_crate: shears::flock::FromCrate<u8>,
+        _from_parent: shears::flock::FromParent<u8>
+    ) -> bool {
+        true
+    }
+
+    fn can_accumulate_attributes(bounds: SplitBounds<String, u8>) -> bool {
+        bounds.b.len() <= 2 && all_distinct(&bounds.b)
+    }
+
     fn can_generate_enum(shaver: Shaver) -> bool {
         !matches!(shaver, Shaver::_Skipped)
     }
+
+    #[allow(deprecated)]
+    fn skips_deprecated_variants(clippers: Clippers) -> bool {
+        !matches!(clippers, Clippers::_Manual)
+    }
+
 }
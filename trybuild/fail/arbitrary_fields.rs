@@ -0,0 +1,19 @@
+use derive_quickcheck_arbitrary::arbitrary_fields;
+
+struct Foo {
+    bar: u8,
+    baz: u8,
+}
+
+fn foo(g: &mut quickcheck::Gen) -> Foo {
+    arbitrary_fields!(
+        Foo {
+            bar,
+            #[arbitrary(default)]
+            baz: 1,
+        },
+        g
+    )
+}
+
+fn main() {}
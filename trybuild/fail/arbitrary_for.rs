@@ -0,0 +1,15 @@
+use derive_quickcheck_arbitrary::arbitrary_for;
+
+#[derive(Clone)]
+struct Fence(u8, bool);
+
+arbitrary_for!(Fence, { 1, 0: default });
+
+#[derive(Clone)]
+struct Gate {
+    open: bool,
+}
+
+arbitrary_for!(Gate, { open: finite });
+
+fn main() {}
@@ -0,0 +1,12 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct Fixed {
+    _data: [u8; Self::LEN],
+}
+
+impl Fixed {
+    const LEN: usize = 4;
+}
+
+fn main() {}
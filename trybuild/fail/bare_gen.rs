@@ -0,0 +1,15 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct Foo {
+    #[arbitrary(gen)]
+    _foo: u8,
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(where)]
+struct Bar {
+    _bar: u8,
+}
+
+fn main() {}
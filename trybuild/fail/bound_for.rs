@@ -0,0 +1,15 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(bound_for(T = "quickcheck::Arbitrary +"))]
+struct Trailing<T> {
+    _animal: T,
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(bound_for(T = quickcheck::Arbitrary))]
+struct Unquoted<T> {
+    _animal: T,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct NotAString {
+    #[arbitrary(byte_len(..8))]
+    _bytes: Vec<u8>,
+}
+
+#[derive(Clone, Arbitrary)]
+struct Both {
+    #[arbitrary(len(..8), byte_len(..8))]
+    _name: String,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct Foo {
+    _f: fn(u8) -> u8,
+}
+
+#[derive(Clone, Arbitrary)]
+struct Bar(std::rc::Rc<dyn Fn()>);
+
+fn main() {}
@@ -0,0 +1,27 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct NotAChar {
+    #[arbitrary(char(ascii))]
+    _letter: u8,
+}
+
+#[derive(Clone, Arbitrary)]
+struct NoCategory {
+    #[arbitrary(char())]
+    _letter: char,
+}
+
+#[derive(Clone, Arbitrary)]
+struct TwoCategories {
+    #[arbitrary(char(ascii, alphanumeric))]
+    _letter: char,
+}
+
+#[derive(Clone, Arbitrary)]
+struct NoRanges {
+    #[arbitrary(char(ranges = []))]
+    _letter: char,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct Foo {
+    #[arbitrary(gen(|_| ()), default)]
+    _foo: (),
+}
+
+fn main() {}
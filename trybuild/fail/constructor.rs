@@ -0,0 +1,28 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(constructor = TooFew::from_parts)]
+struct TooFew {
+    _a: u8,
+    _b: u8,
+}
+
+impl TooFew {
+    fn from_parts(a: u8) -> Self {
+        Self { _a: a, _b: a }
+    }
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(constructor = OnEnum::new)]
+enum OnEnum {
+    _Only,
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(constructor = |a| a)]
+struct NotAPath {
+    _a: u8,
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(distinct_variants)]
+enum CopyPasted {
+    _Shorn { wool: u8, yak: String },
+    _Unshorn { wool: u8, yak: String },
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(distinct_variants)]
+enum Positional {
+    _Left(u8, String),
+    _Right(u8, String),
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(distinct_variants)]
+struct NotAnEnum {
+    _wool: u8,
+}
+
+fn main() {}
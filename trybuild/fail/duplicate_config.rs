@@ -2,7 +2,7 @@ use derive_quickcheck_arbitrary::Arbitrary;
 
 #[derive(Clone, Arbitrary)]
 struct Foo {
-    #[arbitrary(skip)]
+    #[arbitrary(default)]
     #[arbitrary(gen(some_fn))]
     _foo: (),
 }
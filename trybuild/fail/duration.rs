@@ -0,0 +1,15 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct Yakshave {
+    #[arbitrary(duration)]
+    _took: u64,
+}
+
+#[derive(Clone, Arbitrary)]
+struct Conflicting {
+    #[arbitrary(duration(secs = 1..), default)]
+    _took: std::time::Duration,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct NotAnArray {
+    #[arbitrary(each(range(0..=9)))]
+    _digits: Vec<u8>,
+}
+
+#[derive(Clone, Arbitrary)]
+struct Incompatible {
+    #[arbitrary(each(range(0..=9)))]
+    _digits: [f64; 4],
+}
+
+#[derive(Clone, Arbitrary)]
+struct Ordered {
+    #[arbitrary(each(order = 1))]
+    _digits: [u8; 4],
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+fn is_offline() -> bool {
+    false
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(exclude_if = is_offline)]
+enum OnItem {
+    _Only,
+}
+
+#[derive(Clone, Arbitrary)]
+struct OnMember {
+    #[arbitrary(exclude_if = is_offline)]
+    _field: u8,
+}
+
+#[derive(Clone, Arbitrary)]
+enum NotAPath {
+    #[arbitrary(exclude_if = || true)]
+    _Only,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct Foo {
+    #[arbitrary(finite)]
+    _weight: u32,
+}
+
+#[derive(Clone, Arbitrary)]
+struct Bar {
+    #[arbitrary(finite, default)]
+    _weight: f32,
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct Bare {
+    #[arbitrary(gen_iter)]
+    _ages: Vec<u8>,
+}
+
+#[derive(Clone, Arbitrary)]
+struct WithGen {
+    #[arbitrary(gen_iter(|_| 0..3), gen(|_| vec![]))]
+    _ages: Vec<u8>,
+}
+
+#[derive(Clone, Arbitrary)]
+struct NotACollection {
+    #[arbitrary(gen_iter(|_| 0..3))]
+    _age: u8,
+}
+
+fn main() {}
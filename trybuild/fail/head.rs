@@ -0,0 +1,21 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct NotACollection {
+    #[arbitrary(head(gen(|_| 1)))]
+    _checksum: u8,
+}
+
+#[derive(Clone, Arbitrary)]
+struct WrongHead {
+    #[arbitrary(head(gen(|_| String::new())))]
+    _checksums: Vec<u8>,
+}
+
+#[derive(Clone, Arbitrary)]
+struct NoHead {
+    #[arbitrary(tail_len(..4))]
+    _checksums: Vec<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+use std::time::Instant;
+
+#[derive(Clone, Arbitrary)]
+struct Foo {
+    _timer: Instant,
+}
+
+fn main() {}
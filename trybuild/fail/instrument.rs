@@ -0,0 +1,15 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(instrument)]
+struct NotAnEnum {
+    _wool: u8,
+}
+
+#[derive(Clone, Arbitrary)]
+enum OnVariant {
+    #[arbitrary(instrument)]
+    _Only,
+}
+
+fn main() {}
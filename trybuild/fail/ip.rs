@@ -0,0 +1,15 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct AlreadyV4 {
+    #[arbitrary(ipv4)]
+    _addr: std::net::Ipv4Addr,
+}
+
+#[derive(Clone, Arbitrary)]
+struct Both {
+    #[arbitrary(ipv4, ipv6)]
+    _addr: std::net::IpAddr,
+}
+
+fn main() {}
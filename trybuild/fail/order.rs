@@ -0,0 +1,22 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct NotALiteral {
+    #[arbitrary(order = 1 + 1)]
+    _first: u8,
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(order = 0)]
+struct OnTheItem {
+    _first: u8,
+}
+
+#[derive(Clone, Arbitrary)]
+struct Twice {
+    #[arbitrary(order = 0)]
+    #[arbitrary(order = 1)]
+    _first: u8,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct Fraction(#[arbitrary(range(0..1))] f64);
+
+#[derive(Clone, Arbitrary)]
+struct Wide(#[arbitrary(range(0..1))] u128);
+
+#[derive(Clone, Arbitrary)]
+struct Conflicting(#[arbitrary(range(1..), len(1..))] u16);
+
+fn main() {}
@@ -0,0 +1,23 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(rare)]
+struct OnStruct {
+    _horns: u8,
+}
+
+#[derive(Clone, Arbitrary)]
+struct OnMember {
+    #[arbitrary(rare)]
+    _horns: u8,
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(round_robin)]
+enum RoundRobin {
+    Shorn,
+    #[arbitrary(rare)]
+    Wild,
+}
+
+fn main() {}
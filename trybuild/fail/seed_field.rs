@@ -0,0 +1,21 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(seed_field = missing)]
+struct NoSuchField {
+    _seed: u64,
+}
+
+#[derive(Clone, Arbitrary)]
+struct NotSeeded {
+    #[arbitrary(gen(|_, built| built.seed))]
+    _seed: u64,
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(seed_field = seed)]
+enum NotAStruct {
+    Only { seed: u64 },
+}
+
+fn main() {}
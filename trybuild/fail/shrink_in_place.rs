@@ -0,0 +1,15 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct OnMember {
+    #[arbitrary(shrink_in_place)]
+    _names: Vec<String>,
+}
+
+#[derive(Clone, Arbitrary)]
+enum OnVariant {
+    #[arbitrary(shrink_in_place)]
+    Herd(Vec<String>),
+}
+
+fn main() {}
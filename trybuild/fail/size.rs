@@ -0,0 +1,15 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct OnMember {
+    #[arbitrary(size = 10)]
+    _herd: Vec<u8>,
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(size = 10, size = 20)]
+struct Twice {
+    _herd: Vec<u8>,
+}
+
+fn main() {}
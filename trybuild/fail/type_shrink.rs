@@ -0,0 +1,21 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+struct AlreadyShrinks {
+    #[arbitrary(type_shrink)]
+    _count: u8,
+}
+
+#[derive(Clone, Arbitrary)]
+struct OwnShrink {
+    #[arbitrary(gen_fn = PERCENT, type_shrink)]
+    _percent: u8,
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(type_shrink)]
+struct OnItem {
+    _count: u8,
+}
+
+fn main() {}
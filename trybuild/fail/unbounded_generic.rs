@@ -0,0 +1,32 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(error_if_generic)]
+struct Yak<T> {
+    _name: String,
+    _id: T,
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(error_if_generic)]
+enum Herd<T> {
+    Empty,
+    Yaks(Vec<T>),
+}
+
+// one bounded position doesn't cover the other
+#[derive(Clone, Arbitrary)]
+#[arbitrary(error_if_generic)]
+struct Pair<A, B>(#[arbitrary(where(A: quickcheck::Arbitrary))] A, B);
+
+// a generator doesn't bound its return type
+#[derive(Clone, Arbitrary)]
+#[arbitrary(error_if_generic)]
+enum Nested<T> {
+    _Wrapped {
+        #[arbitrary(gen(|_| Vec::new()))]
+        inner: Vec<T>,
+    },
+}
+
+fn main() {}
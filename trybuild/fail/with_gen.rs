@@ -0,0 +1,32 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+use quickcheck::Gen;
+
+struct Shears<'a>(&'a mut Gen);
+
+impl<'a> From<&'a mut Gen> for Shears<'a> {
+    fn from(g: &'a mut Gen) -> Self {
+        Self(g)
+    }
+}
+
+#[derive(Clone, Arbitrary)]
+struct OnMember {
+    #[arbitrary(with_gen(Shears<'_>))]
+    _fleece: u8,
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(with_gen(Shears<'_>))]
+#[arbitrary(with_gen(Shears<'_>))]
+struct Twice {
+    _fleece: u8,
+}
+
+#[derive(Clone, Arbitrary)]
+#[arbitrary(with_gen(Shears<'_>))]
+struct TakesGen {
+    #[arbitrary(gen(|g: &mut Gen| g.size() as u8))]
+    _fleece: u8,
+}
+
+fn main() {}
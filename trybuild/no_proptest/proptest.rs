@@ -0,0 +1,9 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+
+#[derive(Clone, Debug, Arbitrary)]
+#[arbitrary(proptest)]
+struct NoFeature {
+    _id: u8,
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+use derive_quickcheck_arbitrary::Arbitrary;
+use std::marker::PhantomData;
+
+// without `error_if_generic`, a member that isn't generated needs no bounds
+#[derive(Arbitrary)]
+struct Tagged<T: 'static> {
+    _id: u8,
+    #[arbitrary(default)]
+    _tag: PhantomData<T>,
+}
+
+impl<T> Clone for Tagged<T> {
+    fn clone(&self) -> Self {
+        Self {
+            _id: self._id,
+            _tag: PhantomData,
+        }
+    }
+}
+
+// bounds in the parameter list count
+#[derive(Clone, Arbitrary)]
+#[arbitrary(error_if_generic)]
+struct Bounded<T: quickcheck::Arbitrary> {
+    _id: T,
+}
+
+fn generates<T: quickcheck::Arbitrary>() {}
+
+fn main() {
+    generates::<Tagged<String>>();
+    generates::<Bounded<u8>>();
+}
@@ -0,0 +1,28 @@
+use derive_quickcheck_arbitrary::{arbitrary_for, Arbitrary};
+
+#[derive(Clone, Debug, Arbitrary)]
+#[arbitrary(proptest)]
+struct NeedsGen {
+    #[arbitrary(gen(|g| u8::arbitrary(g) / 2))]
+    _half: u8,
+}
+
+#[derive(Clone, Debug, Arbitrary)]
+#[arbitrary(proptest, validate(|_| true))]
+struct Validated {
+    _id: u8,
+}
+
+#[derive(Clone, Debug, Arbitrary)]
+#[arbitrary(proptest)]
+enum Tree {
+    Leaf,
+    Node(Option<Box<Self>>),
+}
+
+#[derive(Clone, Debug)]
+struct Untyped(u8);
+
+arbitrary_for!(#[arbitrary(proptest)] Untyped, { 0 });
+
+fn main() {}